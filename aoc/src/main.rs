@@ -0,0 +1,169 @@
+use aoc_common::{ParseError, Part};
+
+const USAGE: &str = "usage: aoc run [--day <1-8>] [--part <1|2>] [--input <path | ->]";
+
+const DAYS: u32 = 8;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Err(err) = run(&command) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct RunCommand {
+    day: Option<u32>,
+    part: Option<Part>,
+    input: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<RunCommand, String> {
+    let mut args = args.iter();
+    match args.next().map(|a| a.as_str()) {
+        Some("run") => {}
+        Some(other) => return Err(format!("unknown command '{}'", other)),
+        None => return Err("missing command".to_string()),
+    }
+
+    let mut command = RunCommand {
+        day: None,
+        part: None,
+        input: None,
+    };
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for '{}'", flag))?;
+        match flag.as_str() {
+            "--day" => match value.parse::<u32>() {
+                Ok(day) if (1..=DAYS).contains(&day) => command.day = Some(day),
+                _ => return Err(format!("invalid day '{}'", value)),
+            },
+            "--part" => match value.as_str() {
+                "1" => command.part = Some(Part::One),
+                "2" => command.part = Some(Part::Two),
+                _ => return Err(format!("invalid part '{}'", value)),
+            },
+            "--input" => command.input = Some(value.clone()),
+            _ => return Err(format!("unknown option '{}'", flag)),
+        }
+    }
+
+    // a single input file only makes sense for a single day
+    if command.input.is_some() && command.day.is_none() {
+        return Err("--input requires --day".to_string());
+    }
+    Ok(command)
+}
+
+#[test]
+fn test_parse_args_single_day_and_part() {
+    // GIVEN
+    let args: Vec<String> = ["run", "--day", "5", "--part", "2", "--input", "vents.txt"]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+
+    // WHEN
+    let command = parse_args(&args);
+
+    // THEN
+    assert_eq!(
+        command,
+        Ok(RunCommand {
+            day: Some(5),
+            part: Some(Part::Two),
+            input: Some("vents.txt".to_string()),
+        })
+    );
+}
+
+#[test]
+fn test_parse_args_all_days() {
+    // GIVEN
+    let args = vec!["run".to_string()];
+
+    // WHEN
+    let command = parse_args(&args);
+
+    // THEN
+    assert_eq!(
+        command,
+        Ok(RunCommand {
+            day: None,
+            part: None,
+            input: None,
+        })
+    );
+}
+
+#[test]
+fn test_parse_args_rejects_bad_values() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|a| a.to_string()).collect() };
+
+    assert!(parse_args(&to_args(&[])).is_err());
+    assert!(parse_args(&to_args(&["solve"])).is_err());
+    assert!(parse_args(&to_args(&["run", "--day", "9"])).is_err());
+    assert!(parse_args(&to_args(&["run", "--day", "0"])).is_err());
+    assert!(parse_args(&to_args(&["run", "--part", "3"])).is_err());
+    assert!(parse_args(&to_args(&["run", "--day"])).is_err());
+    assert!(parse_args(&to_args(&["run", "--input", "input.txt"])).is_err());
+    assert!(parse_args(&to_args(&["run", "--verbose", "yes"])).is_err());
+}
+
+fn solve(day: u32, input: &str, part: Part) -> Result<String, ParseError> {
+    match day {
+        1 => aoc_common::solve_part::<day1::Day1>(input, part),
+        2 => aoc_common::solve_part::<day2::Day2>(input, part),
+        3 => aoc_common::solve_part::<day3::Day3>(input, part),
+        4 => aoc_common::solve_part::<day4::Day4>(input, part),
+        5 => aoc_common::solve_part::<day5::Day5>(input, part),
+        6 => aoc_common::solve_part::<day6::Day6>(input, part),
+        7 => aoc_common::solve_part::<day7::Day7>(input, part),
+        8 => aoc_common::solve_part::<day8::Day8>(input, part),
+        _ => unreachable!("days are validated when parsing arguments"),
+    }
+}
+
+#[test]
+fn test_solve_dispatches_to_the_requested_day() {
+    assert_eq!(solve(6, "3,4,3,1,2", Part::One), Ok("5934".to_string()));
+    assert_eq!(
+        solve(7, "16,1,2,0,4,2,7,1,2,14", Part::Two),
+        Ok("168".to_string())
+    );
+}
+
+fn run(command: &RunCommand) -> Result<(), String> {
+    let days: Vec<u32> = match command.day {
+        Some(day) => vec![day],
+        None => (1..=DAYS).collect(),
+    };
+    let parts = match command.part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+    for day in days {
+        let path = match &command.input {
+            Some(path) => path.clone(),
+            None => format!("day{}/input.txt", day),
+        };
+        let input = aoc_common::read_input(&path)
+            .map_err(|err| format!("cannot read '{}': {}", path, err))?;
+        for &part in parts.iter() {
+            let answer = solve(day, &input, part).map_err(|err| format!("{}: {}", path, err))?;
+            let part_number = if part == Part::One { 1 } else { 2 };
+            println!("day {} part {} answer is {}", day, part_number, answer);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::io::Read;
+
+/// Reads raw puzzle input from the file at `path`, or from stdin when `path` is
+/// `-` so that solvers can sit at the end of a shell pipeline.
+pub fn read_input(path: &str) -> std::io::Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
+
+#[test]
+fn test_read_input_from_path() {
+    // GIVEN
+    let path = std::env::temp_dir().join("aoc_common_read_input_test.txt");
+    std::fs::write(&path, "3,4,3,1,2\n").unwrap();
+
+    // WHEN
+    let input = read_input(path.to_str().unwrap());
+
+    // THEN
+    assert_eq!(input.unwrap(), "3,4,3,1,2\n");
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Cleans up raw puzzle input so that parsers only ever see `\n`-separated
+/// lines: drops a leading byte order mark, turns CRLF line endings into LF,
+/// strips trailing whitespace from every line and removes trailing blank lines.
+/// Leading whitespace is kept, so line and column numbers still match the file.
+pub fn normalize(raw: &str) -> String {
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+    let lines: Vec<&str> = raw.split('\n').map(|line| line.trim_end()).collect();
+    let last_line = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(0, |index| index + 1);
+    lines[..last_line].join("\n")
+}
+
+#[test]
+fn test_normalize_trailing_newline() {
+    assert_eq!(normalize("199\n200\n208\n"), "199\n200\n208");
+    assert_eq!(normalize("3,4,3,1,2\n"), "3,4,3,1,2");
+}
+
+#[test]
+fn test_normalize_crlf_and_blank_trailing_lines() {
+    assert_eq!(normalize("199\r\n200\r\n\r\n  \r\n"), "199\n200");
+}
+
+#[test]
+fn test_normalize_bom_and_trailing_whitespace() {
+    assert_eq!(
+        normalize("\u{feff}forward 5 \ndown 5\t\n"),
+        "forward 5\ndown 5"
+    );
+}
+
+#[test]
+fn test_normalize_keeps_leading_and_inner_blank_lines() {
+    assert_eq!(normalize("7,4,9\n\n 22 13\n"), "7,4,9\n\n 22 13");
+    assert_eq!(normalize("\n\n"), "");
+}
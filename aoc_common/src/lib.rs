@@ -0,0 +1,165 @@
+use std::fmt::Display;
+
+mod input;
+mod parse;
+
+pub use input::{normalize, read_input};
+pub use parse::{parse_token, ParseError};
+
+/// A puzzle solution: how to turn the raw input into something the two parts
+/// can work on, and how to answer each part from it.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+/// Normalizes raw input (see [`normalize`]) and hands it to the solution's
+/// parser, so that no day has to care about line endings or trailing newlines.
+pub fn parse<S: Solution>(raw: &str) -> Result<S::Input, ParseError> {
+    S::parse(&normalize(raw))
+}
+
+/// Parses the input once and answers both parts.
+pub fn solve<S: Solution>(input: &str) -> Result<(S::Answer1, S::Answer2), ParseError> {
+    let input = parse::<S>(input)?;
+    Ok((S::part1(&input), S::part2(&input)))
+}
+
+/// One of the two questions each puzzle asks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// Parses the input and answers a single part, formatted for display. This
+/// lets callers pick any day at runtime without knowing its answer types.
+pub fn solve_part<S: Solution>(input: &str, part: Part) -> Result<String, ParseError> {
+    let input = parse::<S>(input)?;
+    Ok(match part {
+        Part::One => S::part1(&input).to_string(),
+        Part::Two => S::part2(&input).to_string(),
+    })
+}
+
+/// Reads the input named by the first command line argument (a path, or `-`
+/// for stdin; `input.txt` in the current directory when omitted), solves both
+/// parts and prints the answers.
+pub fn run<S: Solution>() -> std::result::Result<(), std::io::Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let path = input_path(&args)?;
+    let input = read_input(path)?;
+    let (answer1, answer2) = solve::<S>(&input).map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {}", path, err),
+        )
+    })?;
+    println!("part 1 answer is {}, part 2 answer is {}", answer1, answer2);
+    Ok(())
+}
+
+fn input_path(args: &[String]) -> std::result::Result<&str, std::io::Error> {
+    match args {
+        [] => Ok("input.txt"),
+        [path] => Ok(path),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "expected at most one argument: the input path, or - for stdin",
+        )),
+    }
+}
+
+#[test]
+fn test_input_path() {
+    let to_args = |args: &[&str]| -> Vec<String> { args.iter().map(|a| a.to_string()).collect() };
+
+    assert_eq!(input_path(&to_args(&[])).unwrap(), "input.txt");
+    assert_eq!(input_path(&to_args(&["vents.txt"])).unwrap(), "vents.txt");
+    assert_eq!(input_path(&to_args(&["-"])).unwrap(), "-");
+    assert!(input_path(&to_args(&["a.txt", "b.txt"])).is_err());
+}
+
+#[cfg(test)]
+struct Sum;
+
+#[cfg(test)]
+impl Solution for Sum {
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        input
+            .split(",")
+            .map(|n| parse_token(1, input, n, "a number"))
+            .collect()
+    }
+
+    fn part1(input: &Vec<u32>) -> u32 {
+        input.iter().sum()
+    }
+
+    fn part2(input: &Vec<u32>) -> usize {
+        input.len()
+    }
+}
+
+#[test]
+fn test_solve_parses_once_and_answers_both_parts() {
+    // GIVEN
+    let input = "1,2,3,4";
+
+    // WHEN
+    let (answer1, answer2) = solve::<Sum>(input).unwrap();
+
+    // THEN
+    assert_eq!(answer1, 10);
+    assert_eq!(answer2, 4);
+}
+
+#[test]
+fn test_solve_part_formats_the_requested_answer() {
+    // GIVEN
+    let input = "1,2,3,4";
+
+    // WHEN
+    let answer1 = solve_part::<Sum>(input, Part::One);
+    let answer2 = solve_part::<Sum>(input, Part::Two);
+
+    // THEN
+    assert_eq!(answer1, Ok("10".to_string()));
+    assert_eq!(answer2, Ok("4".to_string()));
+}
+
+#[test]
+fn test_solve_reports_parse_errors() {
+    // GIVEN
+    let input = "1,2,three";
+
+    // WHEN
+    let r = solve::<Sum>(input);
+
+    // THEN
+    assert_eq!(
+        r.unwrap_err().to_string(),
+        "line 1, column 5: expected a number, found 'three'"
+    );
+}
+
+#[test]
+fn test_solve_normalizes_input() {
+    // GIVEN
+    let input = "\u{feff}1,2,3,4\r\n\r\n";
+
+    // WHEN
+    let r = solve::<Sum>(input);
+
+    // THEN
+    assert_eq!(r, Ok((10, 4)));
+}
@@ -0,0 +1,100 @@
+use std::{fmt, str::FromStr};
+
+/// Malformed puzzle input: where the offending token sits (1-based line and
+/// column) and what was expected in its place.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Builds an error for `token`, which must be a slice of `line`: the
+    /// column is derived from where the token sits within the line.
+    pub fn at(line_number: usize, line: &str, token: &str, expected: &str) -> ParseError {
+        let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        ParseError {
+            line: line_number,
+            column: if offset <= line.len() { offset + 1 } else { 1 },
+            token: token.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// Builds an error for something missing at the end of `line`.
+    pub fn end_of_line(line_number: usize, line: &str, expected: &str) -> ParseError {
+        ParseError::at(line_number, line, &line[line.len()..], expected)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.token.is_empty() {
+            write!(
+                f,
+                "line {}, column {}: expected {}, found nothing",
+                self.line, self.column, self.expected
+            )
+        } else {
+            write!(
+                f,
+                "line {}, column {}: expected {}, found '{}'",
+                self.line, self.column, self.expected, self.token
+            )
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `token`, a slice of `line`, reporting its position on failure.
+pub fn parse_token<T: FromStr>(
+    line_number: usize,
+    line: &str,
+    token: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::at(line_number, line, token, expected))
+}
+
+#[test]
+fn test_parse_token_reports_column_of_bad_token() {
+    // GIVEN
+    let line = "3,4,x,1";
+    let token = line.split(",").nth(2).unwrap();
+
+    // WHEN
+    let r = parse_token::<u32>(1, line, token, "a number");
+
+    // THEN
+    assert_eq!(
+        r,
+        Err(ParseError {
+            line: 1,
+            column: 5,
+            token: "x".to_string(),
+            expected: "a number".to_string(),
+        })
+    );
+}
+
+#[test]
+fn test_end_of_line_points_past_last_character() {
+    // GIVEN
+    let line = "forward";
+
+    // WHEN
+    let err = ParseError::end_of_line(4, line, "an amount");
+
+    // THEN
+    assert_eq!(err.column, 8);
+    assert_eq!(err.token, "");
+    assert_eq!(
+        err.to_string(),
+        "line 4, column 8: expected an amount, found nothing"
+    );
+}
@@ -0,0 +1,112 @@
+use aoc_common::{parse_token, ParseError, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(measurements: &Vec<u32>) -> u32 {
+        compute_part1(measurements)
+    }
+
+    fn part2(measurements: &Vec<u32>) -> u32 {
+        compute_part2(measurements)
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    input
+        .split("\n")
+        .enumerate()
+        .map(|(index, l)| parse_token(index + 1, l, l, "a depth measurement"))
+        .collect()
+}
+
+#[test]
+fn test_parse_input_reports_malformed_line() {
+    // GIVEN
+    let input = "199\n200\n2o8\n210";
+
+    // WHEN
+    let r = parse_input(input);
+
+    // THEN
+    let err = r.unwrap_err();
+    assert_eq!(err.line, 3);
+    assert_eq!(err.column, 1);
+    assert_eq!(err.token, "2o8");
+}
+
+fn compute_part1(measurements: &[u32]) -> u32 {
+    let downsteps: u32 = measurements
+        .windows(2)
+        .map(|pair| if pair[1] > pair[0] { 1 } else { 0 })
+        .sum();
+    downsteps
+}
+
+fn compute_part2(measurements: &[u32]) -> u32 {
+    let three_measurement_sums: Vec<u32> = measurements
+        .windows(3)
+        .map(|window| window.iter().sum())
+        .collect();
+    compute_part1(&three_measurement_sums)
+}
+
+#[test]
+fn part_1_given_example() {
+    // GIVEN
+    let input = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    // WHEN
+    let r = compute_part1(&input);
+
+    // THEN
+    assert!(r == 7)
+}
+
+#[test]
+fn part_2_given_example() {
+    // GIVEN
+    let input = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    // WHEN
+    let r = compute_part2(&input);
+
+    // THEN
+    assert!(r == 5)
+}
+
+#[test]
+fn test_short_input() {
+    // GIVEN
+    let inputs = ["199", "199\n200", "199\n200\n208", "199\n200\n208\n210"];
+
+    // WHEN
+    let r: Vec<_> = inputs
+        .iter()
+        .map(|input| aoc_common::solve::<Day1>(input))
+        .collect();
+
+    // THEN
+    assert_eq!(r, vec![Ok((0, 0)), Ok((1, 0)), Ok((2, 0)), Ok((3, 1))]);
+}
+
+#[test]
+fn test_solve_tolerates_crlf_bom_and_trailing_newlines() {
+    // GIVEN
+    let input =
+        "\u{feff}199\r\n200\r\n208\r\n210\r\n200\r\n207\r\n240\r\n269\r\n260\r\n263\r\n\r\n";
+
+    // WHEN
+    let r = aoc_common::solve::<Day1>(input);
+
+    // THEN
+    assert_eq!(r, Ok((7, 5)));
+}
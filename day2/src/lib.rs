@@ -0,0 +1,175 @@
+use aoc_common::{parse_token, ParseError, Solution};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<(i32, i32)>;
+    type Answer1 = i32;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<(i32, i32)>, ParseError> {
+        parse_course_input(input)
+    }
+
+    fn part1(course: &Vec<(i32, i32)>) -> i32 {
+        compute_part1(course)
+    }
+
+    fn part2(course: &Vec<(i32, i32)>) -> i64 {
+        compute_part2(course)
+    }
+}
+
+fn parse_course_input(input: &str) -> Result<Vec<(i32, i32)>, ParseError> {
+    input
+        .split("\n")
+        .enumerate()
+        .map(|(index, line)| {
+            let line_number = index + 1;
+            let tokens: Vec<&str> = line.split(" ").collect();
+            let direction = tokens[0];
+            let amount = match tokens.get(1) {
+                Some(amount) => parse_token::<i32>(line_number, line, amount, "an amount")?,
+                None => return Err(ParseError::end_of_line(line_number, line, "an amount")),
+            };
+            if let Some(extra) = tokens.get(2) {
+                return Err(ParseError::at(line_number, line, extra, "end of line"));
+            }
+            match direction {
+                "forward" => Ok((amount, 0)),
+                "up" => Ok((0, -amount)),
+                "down" => Ok((0, amount)),
+                _ => Err(ParseError::at(
+                    line_number,
+                    line,
+                    direction,
+                    "forward, up or down",
+                )),
+            }
+        })
+        .collect()
+}
+
+fn compute_part1(course: &[(i32, i32)]) -> i32 {
+    let final_pos = course
+        .iter()
+        .fold((0 /* horiz pos */, 0 /* depth */), |acc, step| {
+            (acc.0 + step.0, acc.1 + step.1)
+        });
+    final_pos.0 * final_pos.1
+}
+
+fn compute_part2(course: &[(i32, i32)]) -> i64 {
+    let final_pos = course.iter().fold(
+        (
+            0i64, /* horiz pos */
+            0i64, /* depth */
+            0i64, /* aim */
+        ),
+        |acc, step| {
+            (
+                acc.0 + step.0 as i64,
+                acc.1 + step.0 as i64 * acc.2,
+                acc.2 + step.1 as i64,
+            )
+        },
+    );
+    final_pos.0 * final_pos.1
+}
+
+#[test]
+fn part1_parse_input() {
+    // GIVEN
+    let input = [
+        "forward 5",
+        "down 5",
+        "forward 8",
+        "up 3",
+        "down 8",
+        "forward 2",
+    ];
+
+    // WHEN
+    let course = parse_course_input(&input.join("\n")).unwrap();
+
+    // THEN
+    assert!(course.len() == 6);
+    assert!(course[0] == (5, 0));
+    assert!(course[1] == (0, 5));
+    assert!(course[2] == (8, 0));
+    assert!(course[3] == (0, -3));
+    assert!(course[4] == (0, 8));
+    assert!(course[5] == (2, 0));
+}
+
+#[test]
+fn test_parse_reports_malformed_lines() {
+    // GIVEN
+    let input = ["forward 5", "down 5", "backward 8", "up", "down x"];
+
+    // WHEN
+    let unknown_direction = parse_course_input(&input[..3].join("\n"));
+    let missing_amount = parse_course_input(&input[3..4].join("\n"));
+    let bad_amount = parse_course_input(&input[4..].join("\n"));
+
+    // THEN
+    let err = unknown_direction.unwrap_err();
+    assert_eq!((err.line, err.column), (3, 1));
+    assert_eq!(err.token, "backward");
+    let err = missing_amount.unwrap_err();
+    assert_eq!((err.line, err.column), (1, 3));
+    assert_eq!(err.token, "");
+    let err = bad_amount.unwrap_err();
+    assert_eq!((err.line, err.column), (1, 6));
+    assert_eq!(err.token, "x");
+}
+
+#[test]
+fn part1_given_example() {
+    let input = [
+        "forward 5",
+        "down 5",
+        "forward 8",
+        "up 3",
+        "down 8",
+        "forward 2",
+    ];
+
+    // WHEN
+    let course = parse_course_input(&input.join("\n")).unwrap();
+    let answer = compute_part1(&course);
+
+    // THEN
+    assert!(answer == 150);
+}
+
+#[test]
+fn part2_given_example() {
+    let input = [
+        "forward 5",
+        "down 5",
+        "forward 8",
+        "up 3",
+        "down 8",
+        "forward 2",
+    ];
+
+    // WHEN
+    let course = parse_course_input(&input.join("\n")).unwrap();
+    let answer = compute_part2(&course);
+
+    // THEN
+    assert!(answer == 900);
+}
+
+#[test]
+fn test_solve_tolerates_crlf_bom_and_trailing_newlines() {
+    // GIVEN
+    let input = "\u{feff}forward 5\r\ndown 5\r\nforward 8\r\nup 3\r\ndown 8\r\nforward 2 \r\n\r\n";
+
+    // WHEN
+    let r = aoc_common::solve::<Day2>(input);
+
+    // THEN
+    assert_eq!(r, Ok((150, 900)));
+}
@@ -0,0 +1,147 @@
+use aoc_common::{ParseError, Solution};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = (Vec<u32>, usize);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<(Vec<u32>, usize), ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &(Vec<u32>, usize)) -> u32 {
+        compute_part1(&input.0, input.1)
+    }
+
+    fn part2(input: &(Vec<u32>, usize)) -> u32 {
+        compute_part2(&input.0, input.1)
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Vec<u32>, usize), ParseError> {
+    let lines: Vec<&str> = input.split("\n").collect();
+    let bits = lines[0].len();
+    let vec = lines
+        .iter()
+        .enumerate()
+        .map(|(index, &line)| {
+            u32::from_str_radix(line, 2)
+                .map_err(|_| ParseError::at(index + 1, line, line, "a binary number"))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((vec, bits))
+}
+
+#[test]
+fn test_parse_input_reports_malformed_line() {
+    // GIVEN
+    let input = "00100\n11110\n10120";
+
+    // WHEN
+    let r = parse_input(input);
+
+    // THEN
+    let err = r.unwrap_err();
+    assert_eq!((err.line, err.column), (3, 1));
+    assert_eq!(err.token, "10120");
+}
+
+fn compute_part1(input: &[u32], bits_available: usize) -> u32 {
+    let mut gamma_rate = 0;
+    let mut epsilon_rate = 0;
+    for bit_index in 0..bits_available {
+        let occurrences_of_1: u32 = input.iter().map(|n| (n >> bit_index) & 1).sum();
+        if occurrences_of_1 > (input.len() as u32 / 2) {
+            gamma_rate += 1 << bit_index;
+        } else {
+            epsilon_rate += 1 << bit_index;
+        }
+    }
+    gamma_rate * epsilon_rate
+}
+
+fn compute_part2(input: &[u32], bits_available: usize) -> u32 {
+    let mut possible_oxygen_gen_rating = input.to_vec();
+    for bit_index in 0..bits_available {
+        let bit_index = bits_available - bit_index - 1;
+        let occurrences_of_1: u32 = possible_oxygen_gen_rating
+            .iter()
+            .map(|n| (n >> bit_index) & 1)
+            .sum();
+        let mask = 1 << bit_index;
+        let expected =
+            if occurrences_of_1 >= (possible_oxygen_gen_rating.len() as u32 - occurrences_of_1) {
+                mask
+            } else {
+                0
+            };
+        possible_oxygen_gen_rating.retain(|n| (n & mask) == expected);
+        if possible_oxygen_gen_rating.len() == 1 {
+            break;
+        }
+    }
+    let mut possible_co2_scrubber_rating = input.to_vec();
+    for bit_index in 0..bits_available {
+        let bit_index = bits_available - bit_index - 1;
+        let occurrences_of_1: u32 = possible_co2_scrubber_rating
+            .iter()
+            .map(|n| (n >> bit_index) & 1)
+            .sum();
+        let mask = 1 << bit_index;
+        let expected =
+            if occurrences_of_1 >= (possible_co2_scrubber_rating.len() as u32 - occurrences_of_1) {
+                0
+            } else {
+                mask
+            };
+        possible_co2_scrubber_rating.retain(|n| (n & mask) == expected);
+        if possible_co2_scrubber_rating.len() == 1 {
+            break;
+        }
+    }
+    possible_oxygen_gen_rating[0] * possible_co2_scrubber_rating[0]
+}
+
+#[test]
+fn part1_given_example() {
+    let input = [
+        0b00100, 0b11110, 0b10110, 0b10111, 0b10101, 0b01111, 0b00111, 0b11100, 0b10000, 0b11001,
+        0b00010, 0b01010,
+    ];
+    let n_bits = 5;
+
+    // WHEN
+    let answer = compute_part1(&input, n_bits);
+
+    // THEN
+    assert!(answer == 198);
+}
+
+#[test]
+fn part2_given_example() {
+    let input = [
+        0b00100, 0b11110, 0b10110, 0b10111, 0b10101, 0b01111, 0b00111, 0b11100, 0b10000, 0b11001,
+        0b00010, 0b01010,
+    ];
+    let n_bits = 5;
+
+    // WHEN
+    let answer = compute_part2(&input, n_bits);
+
+    // THEN
+    assert!(answer == 230);
+}
+
+#[test]
+fn test_solve_tolerates_crlf_bom_and_trailing_newlines() {
+    // GIVEN
+    let input = "\u{feff}00100\r\n11110\r\n10110\r\n10111\r\n10101\r\n01111\r\n00111\r\n11100\r\n10000\r\n11001\r\n00010\r\n01010\r\n\r\n";
+
+    // WHEN
+    let r = aoc_common::solve::<Day3>(input);
+
+    // THEN
+    assert_eq!(r, Ok((198, 230)));
+}
@@ -0,0 +1,56 @@
+// Compares the sweep line overlap counting with the pairwise one on generated
+// vent fields. Run with `cargo bench -p day5`.
+
+use day5::{count_overlap_points, count_overlap_points_pairwise, Day5, Segment};
+use std::time::{Duration, Instant};
+
+fn generate_vents(count: usize, grid: u64, seed: u64) -> String {
+    let mut seed = seed;
+    let mut next = |bound: u64| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) % bound
+    };
+    (0..count)
+        .map(|_| {
+            let (x1, y1, length) = (next(grid), next(grid), next(grid / 4));
+            let (x2, y2) = match next(3) {
+                0 => ((x1 + length).min(grid), y1),
+                1 => (x1, (y1 + length).min(grid)),
+                _ => {
+                    let length = length.min(grid - x1).min(y1);
+                    (x1 + length, y1 - length)
+                }
+            };
+            format!("{},{} -> {},{}", x1, y1, x2, y2)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn time(count_overlap_points: fn(&[&Segment]) -> u32, vents: &[&Segment]) -> (u32, Duration) {
+    let runs = 5;
+    let start = Instant::now();
+    let mut overlaps = 0;
+    for _ in 0..runs {
+        overlaps = count_overlap_points(vents);
+    }
+    (overlaps, start.elapsed() / runs)
+}
+
+fn main() {
+    println!("segments  overlaps  pairwise      sweep");
+    for count in [100, 250, 500, 1000, 2000] {
+        let input = generate_vents(count, 1000, count as u64);
+        let vents = aoc_common::parse::<Day5>(&input).unwrap();
+        let vents: Vec<&Segment> = vents.iter().collect();
+
+        let (overlaps, sweep) = time(count_overlap_points, &vents);
+        let (_, pairwise) = time(count_overlap_points_pairwise, &vents);
+        println!(
+            "{:>8}  {:>8}  {:>8.2?}  {:>9.2?}",
+            count, overlaps, pairwise, sweep
+        );
+    }
+}
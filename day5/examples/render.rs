@@ -0,0 +1,29 @@
+// Draws the vents of a day 5 input, to eyeball the overlaps. Prints the puzzle
+// style diagram, or writes a PPM heatmap when given an output path:
+//   cargo run -p day5 --example render -- day5/input.txt vents.ppm
+
+use day5::{render_ascii, write_ppm_heatmap, Day5, Segment};
+use std::io::BufWriter;
+
+fn main() -> std::io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let path = args.first().map_or("input.txt", |path| path.as_str());
+    let input = aoc_common::read_input(path)?;
+    let vents = aoc_common::parse::<Day5>(&input).map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {}", path, err),
+        )
+    })?;
+    let vents: Vec<&Segment> = vents.iter().collect();
+    match args.get(1) {
+        Some(output) => {
+            let mut out = BufWriter::new(std::fs::File::create(output)?);
+            write_ppm_heatmap(&vents, &mut out)
+        }
+        None => {
+            println!("{}", render_ascii(&vents));
+            Ok(())
+        }
+    }
+}
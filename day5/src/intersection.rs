@@ -0,0 +1,254 @@
+use crate::Segment;
+use std::fmt;
+
+/// An exact fraction, kept in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Rational {
+    pub fn new(numerator: i128, denominator: i128) -> Rational {
+        assert!(denominator != 0, "denominator must not be zero");
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Rational {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        if self.denominator == 1 {
+            Some(self.numerator)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[test]
+fn test_rational_is_reduced() {
+    assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+    assert_eq!(Rational::new(-3, 2).denominator(), 2);
+    assert_eq!(Rational::new(8, 4).to_integer(), Some(2));
+    assert_eq!(Rational::new(0, -5).to_integer(), Some(0));
+    assert_eq!(Rational::new(7, 2).to_integer(), None);
+    assert_eq!(Rational::new(-7, 2).to_string(), "-7/2");
+}
+
+/// How two segments of any slope meet.
+#[derive(Debug, PartialEq)]
+pub enum Intersection {
+    None,
+    /// A single point with integer coordinates, vents can meet there.
+    LatticePoint((u32, u32)),
+    /// A single point between grid points.
+    Point(Rational, Rational),
+    /// Collinear segments sharing more than one point; the overlap runs in
+    /// the same direction as the first segment.
+    Overlap(Segment),
+}
+
+type Vector = (i128, i128);
+
+fn vector(from: (u32, u32), to: (u32, u32)) -> Vector {
+    (to.0 as i128 - from.0 as i128, to.1 as i128 - from.1 as i128)
+}
+
+fn cross(a: Vector, b: Vector) -> i128 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn dot(a: Vector, b: Vector) -> i128 {
+    a.0 * b.0 + a.1 * b.1
+}
+
+fn contains_point(segment: &Segment, point: (u32, u32)) -> bool {
+    let direction = vector(segment.start, segment.end);
+    let offset = vector(segment.start, point);
+    cross(direction, offset) == 0
+        && 0 <= dot(direction, offset)
+        && dot(direction, offset) <= dot(direction, direction)
+}
+
+/// Intersects two segments of arbitrary slopes with exact arithmetic.
+pub fn intersect(seg_a: &Segment, seg_b: &Segment) -> Intersection {
+    let r = vector(seg_a.start, seg_a.end);
+    let s = vector(seg_b.start, seg_b.end);
+    let qp = vector(seg_a.start, seg_b.start);
+
+    // degenerate segments are single points
+    if r == (0, 0) || s == (0, 0) {
+        let (point, other) = if r == (0, 0) {
+            (seg_a.start, seg_b)
+        } else {
+            (seg_b.start, seg_a)
+        };
+        return if contains_point(other, point) {
+            Intersection::LatticePoint(point)
+        } else {
+            Intersection::None
+        };
+    }
+
+    let denominator = cross(r, s);
+    if denominator == 0 {
+        if cross(qp, r) != 0 {
+            // parallel
+            return Intersection::None;
+        }
+        return collinear_overlap(seg_a, seg_b);
+    }
+
+    // seg_a.start + t * r == seg_b.start + u * s, with t and u in [0, 1]
+    let (mut t, mut u, mut denominator) = (cross(qp, s), cross(qp, r), denominator);
+    if denominator < 0 {
+        (t, u, denominator) = (-t, -u, -denominator);
+    }
+    if t < 0 || t > denominator || u < 0 || u > denominator {
+        return Intersection::None;
+    }
+    let x = Rational::new(seg_a.start.0 as i128 * denominator + t * r.0, denominator);
+    let y = Rational::new(seg_a.start.1 as i128 * denominator + t * r.1, denominator);
+    match (x.to_integer(), y.to_integer()) {
+        (Some(x), Some(y)) => Intersection::LatticePoint((x as u32, y as u32)),
+        _ => Intersection::Point(x, y),
+    }
+}
+
+fn collinear_overlap(seg_a: &Segment, seg_b: &Segment) -> Intersection {
+    // every endpoint projected on seg_a's direction: the overlap goes from the
+    // largest projection of a start to the smallest projection of an end
+    let r = vector(seg_a.start, seg_a.end);
+    let project = |point: (u32, u32)| (dot(r, vector(seg_a.start, point)), point);
+    let (b_first, b_last) = {
+        let (start, end) = (project(seg_b.start), project(seg_b.end));
+        if start.0 <= end.0 {
+            (start, end)
+        } else {
+            (end, start)
+        }
+    };
+    let from = if b_first.0 > 0 {
+        b_first
+    } else {
+        project(seg_a.start)
+    };
+    let to = if b_last.0 < dot(r, r) {
+        b_last
+    } else {
+        project(seg_a.end)
+    };
+    if from.0 > to.0 {
+        Intersection::None
+    } else if from.0 == to.0 {
+        Intersection::LatticePoint(from.1)
+    } else {
+        Intersection::Overlap(Segment {
+            start: from.1,
+            end: to.1,
+        })
+    }
+}
+
+#[cfg(test)]
+use crate::segment;
+
+#[test]
+fn test_intersect_crossing_segments() {
+    // lattice point, any slope
+    assert_eq!(
+        intersect(&segment(0, 0, 4, 2), &segment(2, 0, 2, 4)),
+        Intersection::LatticePoint((2, 1))
+    );
+    // between grid points
+    assert_eq!(
+        intersect(&segment(0, 0, 1, 1), &segment(0, 1, 1, 0)),
+        Intersection::Point(Rational::new(1, 2), Rational::new(1, 2))
+    );
+    assert_eq!(
+        intersect(&segment(0, 0, 6, 3), &segment(0, 3, 6, 0)),
+        Intersection::Point(Rational::new(3, 1), Rational::new(3, 2))
+    );
+    // touching at an endpoint
+    assert_eq!(
+        intersect(&segment(0, 0, 3, 1), &segment(3, 1, 5, 7)),
+        Intersection::LatticePoint((3, 1))
+    );
+    // lines cross outside of the segments
+    assert_eq!(
+        intersect(&segment(0, 0, 2, 1), &segment(5, 0, 5, 9)),
+        Intersection::None
+    );
+}
+
+#[test]
+fn test_intersect_parallel_and_collinear_segments() {
+    // parallel
+    assert_eq!(
+        intersect(&segment(0, 0, 6, 3), &segment(0, 1, 6, 4)),
+        Intersection::None
+    );
+    // collinear, overlapping, second one reversed
+    assert_eq!(
+        intersect(&segment(0, 0, 6, 3), &segment(10, 5, 4, 2)),
+        Intersection::Overlap(segment(4, 2, 6, 3))
+    );
+    assert_eq!(
+        intersect(&segment(10, 5, 0, 0), &segment(4, 2, 6, 3)),
+        Intersection::Overlap(segment(6, 3, 4, 2))
+    );
+    // collinear, touching
+    assert_eq!(
+        intersect(&segment(0, 0, 2, 1), &segment(4, 2, 2, 1)),
+        Intersection::LatticePoint((2, 1))
+    );
+    // collinear, apart
+    assert_eq!(
+        intersect(&segment(0, 0, 2, 1), &segment(4, 2, 6, 3)),
+        Intersection::None
+    );
+}
+
+#[test]
+fn test_intersect_single_points() {
+    assert_eq!(
+        intersect(&segment(2, 1, 2, 1), &segment(0, 0, 4, 2)),
+        Intersection::LatticePoint((2, 1))
+    );
+    assert_eq!(
+        intersect(&segment(0, 0, 4, 2), &segment(1, 1, 1, 1)),
+        Intersection::None
+    );
+    assert_eq!(
+        intersect(&segment(3, 3, 3, 3), &segment(3, 3, 3, 3)),
+        Intersection::LatticePoint((3, 3))
+    );
+}
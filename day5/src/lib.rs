@@ -0,0 +1,291 @@
+use aoc_common::{parse_token, ParseError, Solution};
+use std::fmt::Debug;
+
+mod intersection;
+mod overlap;
+mod render;
+mod sweep;
+
+pub use intersection::{intersect, Intersection, Rational};
+pub use overlap::count_overlap_points_pairwise;
+pub use render::{density, render_ascii, write_ppm_heatmap};
+pub use sweep::count_overlap_points;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Segment>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Segment>, ParseError> {
+        parse_input(input.split("\n").collect())
+    }
+
+    fn part1(vents: &Vec<Segment>) -> u32 {
+        compute_part1(vents)
+    }
+
+    fn part2(vents: &Vec<Segment>) -> u32 {
+        compute_part2(vents)
+    }
+}
+
+/// A line of vents, from `start` to `end` both included.
+#[derive(Debug, PartialEq)]
+pub struct Segment {
+    start: (u32, u32),
+    end: (u32, u32),
+}
+
+impl Segment {
+    /// Segment going from `start` to `end`.
+    pub fn new(start: (u32, u32), end: (u32, u32)) -> Segment {
+        Segment { start, end }
+    }
+
+    /// First point of the segment, as written in the input.
+    pub fn start(&self) -> (u32, u32) {
+        self.start
+    }
+
+    /// Last point of the segment, as written in the input.
+    pub fn end(&self) -> (u32, u32) {
+        self.end
+    }
+
+    // same points, running towards increasing x, or increasing y when vertical
+    fn normalized(&self) -> Segment {
+        if self.start <= self.end {
+            Segment {
+                start: self.start,
+                end: self.end,
+            }
+        } else {
+            Segment {
+                start: self.end,
+                end: self.start,
+            }
+        }
+    }
+}
+
+// segment from (x1, y1) to (x2, y2), for tests
+#[cfg(test)]
+fn segment(x1: u32, y1: u32, x2: u32, y2: u32) -> Segment {
+    Segment::new((x1, y1), (x2, y2))
+}
+
+fn parse_input(lines: Vec<&str>) -> Result<Vec<Segment>, ParseError> {
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, segment_line)| {
+            let line_number = index + 1;
+            let segment = segment_line.trim();
+            let (start, end) = segment.split_once(" -> ").ok_or_else(|| {
+                ParseError::at(line_number, segment_line, segment, "'x1,y1 -> x2,y2'")
+            })?;
+            Ok(Segment {
+                start: parse_point(line_number, segment_line, start)?,
+                end: parse_point(line_number, segment_line, end)?,
+            })
+        })
+        .collect()
+}
+
+fn parse_point(line_number: usize, line: &str, point: &str) -> Result<(u32, u32), ParseError> {
+    let (x, y) = point
+        .split_once(",")
+        .ok_or_else(|| ParseError::at(line_number, line, point, "a point 'x,y'"))?;
+    Ok((
+        parse_token(line_number, line, x, "a coordinate")?,
+        parse_token(line_number, line, y, "a coordinate")?,
+    ))
+}
+
+#[test]
+fn test_parse_given_example_input() {
+    // GIVEN
+    let input = "0,9 -> 5,9
+    8,0 -> 0,8
+    9,4 -> 3,4
+    2,2 -> 2,1
+    7,0 -> 7,4
+    6,4 -> 2,0
+    0,9 -> 2,9
+    3,4 -> 1,4
+    0,0 -> 8,8
+    5,5 -> 8,2";
+    let lines: Vec<&str> = input.split("\n").collect();
+
+    // WHEN
+    let vents = parse_input(lines).unwrap();
+
+    // THEN
+    assert!(vents.len() == 10);
+    assert!(vents[0].start == (0, 9));
+    assert!(vents[0].end == (5, 9));
+    assert!(vents[9].start == (5, 5));
+    assert!(vents[9].end == (8, 2));
+}
+
+#[test]
+fn test_segment_new() {
+    // WHEN
+    let segment = Segment::new((0, 9), (5, 9));
+
+    // THEN
+    assert_eq!((segment.start(), segment.end()), ((0, 9), (5, 9)));
+    assert_eq!(parse_input(vec!["0,9 -> 5,9"]), Ok(vec![segment]));
+}
+
+#[test]
+fn test_parse_reports_malformed_segment() {
+    // GIVEN
+    let input = "0,9 -> 5,9
+    8,0 -> 0,8
+    9,4 => 3,4
+    2,2 -> 2,x";
+
+    // WHEN
+    let bad_arrow = parse_input(input.split("\n").take(3).collect());
+    let bad_coordinate = parse_input(input.split("\n").skip(3).collect());
+
+    // THEN
+    let err = bad_arrow.unwrap_err();
+    assert_eq!((err.line, err.column), (3, 5));
+    assert_eq!(err.token, "9,4 => 3,4");
+    let err = bad_coordinate.unwrap_err();
+    assert_eq!((err.line, err.column), (1, 14));
+    assert_eq!(err.token, "x");
+}
+
+fn is_horizontal_segment(segment: &Segment) -> bool {
+    segment.start.1 == segment.end.1
+}
+
+fn is_vertical_segment(segment: &Segment) -> bool {
+    segment.start.0 == segment.end.0
+}
+
+// only 45 degrees diagonals are considered
+fn is_diagonal_segment(segment: &Segment) -> bool {
+    segment.start.0.abs_diff(segment.end.0) == segment.start.1.abs_diff(segment.end.1)
+}
+
+#[test]
+fn test_segment_classification() {
+    // GIVEN
+    let horizontal = Segment {
+        start: (9, 4),
+        end: (3, 4),
+    };
+    let vertical = Segment {
+        start: (2, 2),
+        end: (2, 1),
+    };
+    let diagonal = Segment {
+        start: (5, 5),
+        end: (8, 2),
+    };
+    let other = Segment {
+        start: (0, 0),
+        end: (2, 1),
+    };
+
+    // THEN
+    assert!(is_horizontal_segment(&horizontal) && !is_diagonal_segment(&horizontal));
+    assert!(is_vertical_segment(&vertical) && !is_diagonal_segment(&vertical));
+    assert!(is_diagonal_segment(&diagonal));
+    assert!(!is_horizontal_segment(&diagonal) && !is_vertical_segment(&diagonal));
+    assert!(!is_horizontal_segment(&other) && !is_vertical_segment(&other));
+    assert!(!is_diagonal_segment(&other));
+}
+
+fn compute_part1(vents: &[Segment]) -> u32 {
+    // keeping only horizontal and vertical lines
+    let vents: Vec<&Segment> = vents
+        .iter()
+        .filter(|seg| is_horizontal_segment(seg) || is_vertical_segment(seg))
+        .collect();
+    count_overlap_points(&vents)
+}
+
+fn compute_part2(vents: &[Segment]) -> u32 {
+    // horizontal, vertical and diagonal lines
+    let vents: Vec<&Segment> = vents
+        .iter()
+        .filter(|seg| {
+            is_horizontal_segment(seg) || is_vertical_segment(seg) || is_diagonal_segment(seg)
+        })
+        .collect();
+    count_overlap_points(&vents)
+}
+
+#[test]
+fn part_1_given_example() {
+    // GIVEN
+    let input = "0,9 -> 5,9
+    8,0 -> 0,8
+    9,4 -> 3,4
+    2,2 -> 2,1
+    7,0 -> 7,4
+    6,4 -> 2,0
+    0,9 -> 2,9
+    3,4 -> 1,4
+    0,0 -> 8,8
+    5,5 -> 8,2";
+
+    // WHEN
+    let vents = parse_input(input.split("\n").collect()).unwrap();
+    let r = compute_part1(&vents);
+
+    // THEN
+    assert!(r == 5)
+}
+
+#[test]
+fn part_2_given_example() {
+    // GIVEN
+    let input = "0,9 -> 5,9
+    8,0 -> 0,8
+    9,4 -> 3,4
+    2,2 -> 2,1
+    7,0 -> 7,4
+    6,4 -> 2,0
+    0,9 -> 2,9
+    3,4 -> 1,4
+    0,0 -> 8,8
+    5,5 -> 8,2";
+
+    // WHEN
+    let vents = parse_input(input.split("\n").collect()).unwrap();
+    let r = compute_part2(&vents);
+
+    // THEN
+    assert!(r == 12)
+}
+
+#[test]
+fn test_solve_tolerates_crlf_bom_and_trailing_newlines() {
+    // GIVEN
+    let input = "0,9 -> 5,9
+    8,0 -> 0,8
+    9,4 -> 3,4
+    2,2 -> 2,1
+    7,0 -> 7,4
+    6,4 -> 2,0
+    0,9 -> 2,9
+    3,4 -> 1,4
+    0,0 -> 8,8
+    5,5 -> 8,2
+";
+    let input = format!("\u{feff}{}\r\n", input.replace("\n", "\r\n"));
+
+    // WHEN
+    let r = aoc_common::solve::<Day5>(&input);
+
+    // THEN
+    assert_eq!(r, Ok((5, 12)));
+}
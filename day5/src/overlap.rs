@@ -0,0 +1,336 @@
+use crate::{
+    intersect, is_diagonal_segment, is_horizontal_segment, is_vertical_segment, Intersection,
+    Segment,
+};
+use std::collections::HashSet;
+
+// Points shared by pairs of horizontal, vertical or 45 degrees segments.
+//
+// Segments are normalized first (see `Segment::normalized`), so that they all
+// run towards increasing x, or increasing y when vertical. Along any of these
+// lines, points are then ordered as plain (x, y) tuples: an overlap goes from
+// the largest start to the smallest end, and a point lies on a segment when
+// it is on its line and between its endpoints.
+
+fn is_grid_segment(segment: &Segment) -> bool {
+    is_horizontal_segment(segment) || is_vertical_segment(segment) || is_diagonal_segment(segment)
+}
+
+fn offset(from: (u32, u32), to: (u32, u32)) -> (i64, i64) {
+    (to.0 as i64 - from.0 as i64, to.1 as i64 - from.1 as i64)
+}
+
+// every endpoint of both segments lies on a single line
+fn on_same_line(seg_a: &Segment, seg_b: &Segment) -> bool {
+    let line = if seg_a.start == seg_a.end {
+        seg_b
+    } else {
+        seg_a
+    };
+    let direction = offset(line.start, line.end);
+    [seg_a.start, seg_a.end, seg_b.start, seg_b.end]
+        .iter()
+        .all(|&point| {
+            let offset = offset(line.start, point);
+            direction.0 * offset.1 == direction.1 * offset.0
+        })
+}
+
+// both segments must be normalized
+fn horiz_vert_intersection(horiz_segment: &Segment, vert_segment: &Segment) -> Option<(u32, u32)> {
+    let point = (vert_segment.start.0, horiz_segment.start.1);
+    if horiz_segment.start <= point
+        && point <= horiz_segment.end
+        && vert_segment.start <= point
+        && point <= vert_segment.end
+    {
+        Some(point)
+    } else {
+        None
+    }
+}
+
+// both segments must be normalized
+fn same_direction_overlap(seg_a: &Segment, seg_b: &Segment) -> Option<Segment> {
+    if !on_same_line(seg_a, seg_b) {
+        return None;
+    }
+    let start = seg_a.start.max(seg_b.start);
+    let end = seg_a.end.min(seg_b.end);
+    if start <= end {
+        Some(Segment { start, end })
+    } else {
+        None
+    }
+}
+
+fn overlap_points_from_segment(segment: &Segment) -> Vec<(u32, u32)> {
+    if !is_grid_segment(segment) {
+        return vec![];
+    }
+    let (dx, dy) = offset(segment.start, segment.end);
+    let (step_x, step_y) = (dx.signum(), dy.signum());
+    (0..=dx.abs().max(dy.abs()))
+        .map(|n| {
+            (
+                (segment.start.0 as i64 + n * step_x) as u32,
+                (segment.start.1 as i64 + n * step_y) as u32,
+            )
+        })
+        .collect()
+}
+
+// both segments must be normalized
+fn shared_points(seg_a: &Segment, seg_b: &Segment) -> Vec<(u32, u32)> {
+    if let Some(overlap) = same_direction_overlap(seg_a, seg_b) {
+        return overlap_points_from_segment(&overlap);
+    }
+    let crossing = if is_horizontal_segment(seg_a) && is_vertical_segment(seg_b) {
+        horiz_vert_intersection(seg_a, seg_b)
+    } else if is_vertical_segment(seg_a) && is_horizontal_segment(seg_b) {
+        horiz_vert_intersection(seg_b, seg_a)
+    } else {
+        match intersect(seg_a, seg_b) {
+            Intersection::LatticePoint(point) => Some(point),
+            _ => None,
+        }
+    };
+    crossing.into_iter().collect()
+}
+
+/// Number of grid points covered by at least two of the horizontal, vertical
+/// or 45 degrees `vents`, comparing every pair of segments; segments at any
+/// other angle are ignored. Kept as a reference for [`count_overlap_points`].
+///
+/// [`count_overlap_points`]: crate::count_overlap_points
+pub fn count_overlap_points_pairwise(vents: &[&Segment]) -> u32 {
+    let vents: Vec<Segment> = vents
+        .iter()
+        .filter(|seg| is_grid_segment(seg))
+        .map(|seg| seg.normalized())
+        .collect();
+    let mut intersection_points = HashSet::new();
+    for (index, segment) in vents.iter().enumerate() {
+        for other_segment in vents[index + 1..].iter() {
+            intersection_points.extend(shared_points(segment, other_segment));
+        }
+    }
+    intersection_points.len() as u32
+}
+
+/// Brute-force reference for the overlap counts: draws every vent on a grid
+/// and counts the cells drawn at least twice.
+#[cfg(test)]
+pub(crate) fn count_overlap_points_grid(vents: &[&Segment]) -> u32 {
+    let vents: Vec<&&Segment> = vents.iter().filter(|seg| is_grid_segment(seg)).collect();
+    let width = vents.iter().map(|seg| seg.start.0.max(seg.end.0) + 1).max();
+    let height = vents.iter().map(|seg| seg.start.1.max(seg.end.1) + 1).max();
+    let (Some(width), Some(height)) = (width, height) else {
+        return 0;
+    };
+    let mut grid = vec![vec![0u32; width as usize]; height as usize];
+    for segment in vents {
+        let (mut x, mut y) = segment.start;
+        loop {
+            grid[y as usize][x as usize] += 1;
+            if (x, y) == segment.end {
+                break;
+            }
+            x = (x as i64 + (segment.end.0 as i64 - x as i64).signum()) as u32;
+            y = (y as i64 + (segment.end.1 as i64 - y as i64).signum()) as u32;
+        }
+    }
+    grid.iter().flatten().filter(|&&n| n >= 2).count() as u32
+}
+
+#[cfg(test)]
+use crate::segment;
+
+#[test]
+fn test_normalized() {
+    assert_eq!(segment(9, 4, 3, 4).normalized(), segment(3, 4, 9, 4));
+    assert_eq!(segment(2, 2, 2, 1).normalized(), segment(2, 1, 2, 2));
+    assert_eq!(segment(5, 5, 8, 2).normalized(), segment(5, 5, 8, 2));
+    assert_eq!(segment(8, 8, 0, 0).normalized(), segment(0, 0, 8, 8));
+}
+
+#[test]
+fn test_horiz_vert_intersection() {
+    // GIVEN
+    let seg_a = segment(5, 4, 0, 4).normalized();
+    let seg_b = segment(3, 6, 3, 2).normalized();
+    let seg_c = segment(6, 2, 6, 6).normalized();
+
+    // WHEN
+    let intersect = horiz_vert_intersection(&seg_a, &seg_b);
+    let missed = horiz_vert_intersection(&seg_a, &seg_c);
+
+    // THEN
+    assert_eq!(intersect, Some((3, 4)));
+    assert_eq!(missed, None);
+}
+
+#[test]
+fn test_diagonal_points_from_segment() {
+    // GIVEN
+    let seg = segment(9, 7, 7, 9);
+
+    // WHEN
+    let points = overlap_points_from_segment(&seg);
+
+    // THEN
+    assert_eq!(points, vec![(9, 7), (8, 8), (7, 9)]);
+}
+
+#[test]
+fn test_reversed_points_from_segment() {
+    assert_eq!(
+        overlap_points_from_segment(&segment(3, 4, 1, 4)),
+        vec![(3, 4), (2, 4), (1, 4)]
+    );
+    assert_eq!(
+        overlap_points_from_segment(&segment(2, 2, 2, 1)),
+        vec![(2, 2), (2, 1)]
+    );
+    assert!(overlap_points_from_segment(&segment(0, 0, 4, 2)).is_empty());
+}
+
+#[test]
+fn test_diagonal_shared_points() {
+    // GIVEN
+    let diag = segment(0, 0, 8, 8).normalized();
+    let crossing_diag = segment(8, 0, 0, 8).normalized();
+    let collinear_diag = segment(10, 10, 6, 6).normalized();
+    let vertical = segment(7, 0, 7, 4).normalized();
+    let missed_diag = segment(1, 0, 0, 1).normalized();
+
+    // WHEN
+    let crossing = shared_points(&diag, &crossing_diag);
+    let collinear = shared_points(&diag, &collinear_diag);
+    let with_vertical = shared_points(&diag, &vertical);
+    let missed = shared_points(&diag, &missed_diag);
+
+    // THEN
+    assert_eq!(crossing, vec![(4, 4)]);
+    assert_eq!(collinear, vec![(6, 6), (7, 7), (8, 8)]);
+    assert!(with_vertical.is_empty());
+    assert!(missed.is_empty());
+}
+
+#[test]
+fn test_same_direction_overlap() {
+    // GIVEN
+    let seg_a = segment(5, 9, 0, 9).normalized();
+    let seg_b = segment(0, 9, 2, 9).normalized();
+
+    // WHEN
+    let overlap = same_direction_overlap(&seg_a, &seg_b);
+
+    // THEN
+    assert_eq!(overlap, Some(segment(0, 9, 2, 9)));
+}
+
+#[test]
+fn test_same_direction_overlap_vertical() {
+    // GIVEN
+    let seg_a = segment(3, 0, 3, 6).normalized();
+    let seg_b = segment(3, 8, 3, 4).normalized();
+    let seg_c = segment(4, 0, 4, 6).normalized();
+
+    // WHEN
+    let overlap = same_direction_overlap(&seg_a, &seg_b);
+    let parallel = same_direction_overlap(&seg_a, &seg_c);
+
+    // THEN
+    assert_eq!(overlap, Some(segment(3, 4, 3, 6)));
+    assert_eq!(parallel, None);
+}
+
+#[test]
+fn test_same_direction_overlap_same_segment() {
+    // GIVEN
+    let seg_a = segment(0, 9, 5, 9);
+
+    // WHEN
+    let overlap = same_direction_overlap(&seg_a, &seg_a);
+    assert!(overlap.is_some());
+    let overlap_points = overlap_points_from_segment(&overlap.unwrap());
+
+    // THEN
+    assert!(overlap_points.len() == 6);
+}
+
+#[test]
+fn test_same_direction_overlap_segment_and_single_point() {
+    // GIVEN
+    let seg_a = segment(0, 9, 5, 9);
+    let seg_b = segment(0, 9, 0, 9);
+    let seg_c = segment(0, 8, 0, 8);
+
+    // WHEN
+    let overlap = same_direction_overlap(&seg_a, &seg_b);
+    let apart = same_direction_overlap(&seg_c, &seg_a);
+
+    // THEN
+    assert_eq!(overlap, Some(segment(0, 9, 0, 9)));
+    assert_eq!(apart, None);
+}
+
+#[cfg(test)]
+fn grid_segment() -> impl proptest::strategy::Strategy<Value = Segment> {
+    use proptest::prelude::*;
+    (0u32..16, 0u32..16, 0u32..8, 0u8..5, any::<bool>()).prop_map(
+        |(x, y, length, direction, reversed)| {
+            let (start, end) = match direction {
+                0 => ((x, y), (x + length, y)),
+                1 => ((x, y), (x, y + length)),
+                2 => ((x, y), (x + length, y + length)),
+                3 => ((x, y + length), (x + length, y)),
+                // any other angle, to be ignored
+                _ => ((x, y), (x + 2 * length + 1, y + length)),
+            };
+            if reversed {
+                segment(end.0, end.1, start.0, start.1)
+            } else {
+                segment(start.0, start.1, end.0, end.1)
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn prop_shared_points_are_common_to_both_segments(
+        seg_a in grid_segment(),
+        seg_b in grid_segment(),
+    ) {
+        let points_a: HashSet<(u32, u32)> = overlap_points_from_segment(&seg_a).into_iter().collect();
+        let points_b: HashSet<(u32, u32)> = overlap_points_from_segment(&seg_b).into_iter().collect();
+        let expected: HashSet<(u32, u32)> = points_a.intersection(&points_b).copied().collect();
+
+        let shared = if is_grid_segment(&seg_a) && is_grid_segment(&seg_b) {
+            shared_points(&seg_a.normalized(), &seg_b.normalized())
+        } else {
+            vec![]
+        };
+
+        proptest::prop_assert_eq!(shared.into_iter().collect::<HashSet<_>>(), expected);
+    }
+
+    #[test]
+    fn prop_pairwise_count_matches_grid(
+        vents in proptest::collection::vec(grid_segment(), 0..40),
+    ) {
+        let vents: Vec<&Segment> = vents.iter().collect();
+        proptest::prop_assert_eq!(count_overlap_points_pairwise(&vents), count_overlap_points_grid(&vents));
+    }
+
+    #[test]
+    fn prop_sweep_count_matches_grid(
+        vents in proptest::collection::vec(grid_segment(), 0..40),
+    ) {
+        let vents: Vec<&Segment> = vents.iter().collect();
+        proptest::prop_assert_eq!(crate::count_overlap_points(&vents), count_overlap_points_grid(&vents));
+    }
+}
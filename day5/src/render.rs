@@ -0,0 +1,165 @@
+use crate::{is_diagonal_segment, is_horizontal_segment, is_vertical_segment, Segment};
+use std::io::Write;
+
+/// Number of vents covering every grid point, indexed by `[y][x]`, from the
+/// origin to the furthest endpoint. Segments that are not horizontal, vertical
+/// or 45 degrees are left out.
+pub fn density(vents: &[&Segment]) -> Vec<Vec<u32>> {
+    let vents: Vec<&&Segment> = vents
+        .iter()
+        .filter(|seg| {
+            is_horizontal_segment(seg) || is_vertical_segment(seg) || is_diagonal_segment(seg)
+        })
+        .collect();
+    let width = vents.iter().map(|seg| seg.start.0.max(seg.end.0) + 1).max();
+    let height = vents.iter().map(|seg| seg.start.1.max(seg.end.1) + 1).max();
+    let (Some(width), Some(height)) = (width, height) else {
+        return vec![];
+    };
+    let mut grid = vec![vec![0u32; width as usize]; height as usize];
+    for segment in vents {
+        let (mut x, mut y) = segment.start;
+        loop {
+            grid[y as usize][x as usize] += 1;
+            if (x, y) == segment.end {
+                break;
+            }
+            x = (x as i64 + (segment.end.0 as i64 - x as i64).signum()) as u32;
+            y = (y as i64 + (segment.end.1 as i64 - y as i64).signum()) as u32;
+        }
+    }
+    grid
+}
+
+/// The vent diagram as drawn in the puzzle: `.` where there is no vent,
+/// otherwise the number of vents, or `#` past 9. Meant for small inputs.
+pub fn render_ascii(vents: &[&Segment]) -> String {
+    density(vents)
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&count| match count {
+                    0 => '.',
+                    1..=9 => char::from_digit(count, 10).unwrap(),
+                    _ => '#',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// black for no vent, then red, yellow and white as the density increases
+fn heat_color(count: u32, max_count: u32) -> [u8; 3] {
+    if count == 0 {
+        return [0, 0, 0];
+    }
+    // a single vent is still visible
+    let heat = 0.25 + 0.75 * count as f64 / max_count as f64;
+    let channel = |from: f64| ((heat * 3.0 - from).clamp(0.0, 1.0) * 255.0).round() as u8;
+    [channel(0.0), channel(1.0), channel(2.0)]
+}
+
+/// Writes the vent density as a binary PPM heatmap, one pixel per grid point,
+/// for inputs too large to read as text.
+pub fn write_ppm_heatmap<W: Write>(vents: &[&Segment], out: &mut W) -> std::io::Result<()> {
+    let grid = density(vents);
+    let height = grid.len();
+    let width = grid.first().map_or(0, |row| row.len());
+    let max_count = grid.iter().flatten().copied().max().unwrap_or(0);
+    write!(out, "P6\n{} {}\n255\n", width, height)?;
+    let pixels: Vec<u8> = grid
+        .iter()
+        .flatten()
+        .flat_map(|&count| heat_color(count, max_count))
+        .collect();
+    out.write_all(&pixels)
+}
+
+#[cfg(test)]
+fn example_vents() -> Vec<Segment> {
+    let input = "0,9 -> 5,9
+    8,0 -> 0,8
+    9,4 -> 3,4
+    2,2 -> 2,1
+    7,0 -> 7,4
+    6,4 -> 2,0
+    0,9 -> 2,9
+    3,4 -> 1,4
+    0,0 -> 8,8
+    5,5 -> 8,2";
+    crate::parse_input(input.split("\n").collect()).unwrap()
+}
+
+#[test]
+fn test_render_ascii_given_example() {
+    // GIVEN
+    let vents = example_vents();
+    let straight: Vec<&Segment> = vents
+        .iter()
+        .filter(|seg| is_horizontal_segment(seg) || is_vertical_segment(seg))
+        .collect();
+    let all: Vec<&Segment> = vents.iter().collect();
+
+    // WHEN
+    let part1 = render_ascii(&straight);
+    let part2 = render_ascii(&all);
+
+    // THEN
+    assert_eq!(
+        part1,
+        ".......1..
+..1....1..
+..1....1..
+.......1..
+.112111211
+..........
+..........
+..........
+..........
+222111...."
+    );
+    assert_eq!(
+        part2,
+        "1.1....11.
+.111...2..
+..2.1.111.
+...1.2.2..
+.112313211
+...1.2....
+..1...1...
+.1.....1..
+1.......1.
+222111...."
+    );
+}
+
+#[test]
+fn test_write_ppm_heatmap() {
+    // GIVEN
+    let vents = [
+        Segment {
+            start: (0, 0),
+            end: (2, 0),
+        },
+        Segment {
+            start: (1, 1),
+            end: (1, 0),
+        },
+    ];
+    let vents: Vec<&Segment> = vents.iter().collect();
+
+    // WHEN
+    let mut out = vec![];
+    write_ppm_heatmap(&vents, &mut out).unwrap();
+
+    // THEN
+    let header = b"P6\n3 2\n255\n";
+    assert_eq!(&out[..header.len()], header);
+    let pixels = &out[header.len()..];
+    assert_eq!(pixels.len(), 3 * 2 * 3);
+    // the overlap is the hottest point, empty points are black
+    assert_eq!(&pixels[3..6], &[255, 255, 255]);
+    assert_eq!(&pixels[0..3], &[255, 223, 0]);
+    assert_eq!(&pixels[9..12], &[0, 0, 0]);
+}
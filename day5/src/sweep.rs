@@ -0,0 +1,368 @@
+use crate::Segment;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+// Counting the points covered by at least two vent lines, without comparing
+// every pair of segments.
+//
+// Every horizontal, vertical or 45 degrees segment lies on a line
+// `a*x + b*y = key`, so segments are first grouped by line. Along a single
+// line each segment is a range of positions (x, or y for vertical lines) and a
+// 1-D sweep gives both the merged ranges and the ones covered twice.
+//
+// Lines of different orientations cross in at most one point. For a pair of
+// orientations, a range on the first line only meets the second line for a
+// range of its keys (and vice versa), so the crossings are the classic
+// intersections of "horizontal" and "vertical" segments in key space, found
+// with a sweep over an ordered set in O((n + k) log n) for k crossings.
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+enum Orientation {
+    Horizontal,
+    Vertical,
+    Diagonal,
+    AntiDiagonal,
+}
+
+const ORIENTATIONS: [Orientation; 4] = [
+    Orientation::Horizontal,
+    Orientation::Vertical,
+    Orientation::Diagonal,
+    Orientation::AntiDiagonal,
+];
+
+// (a, b) in the line equation a*x + b*y = key
+fn coefficients(orientation: Orientation) -> (i64, i64) {
+    match orientation {
+        Orientation::Horizontal => (0, 1),
+        Orientation::Vertical => (1, 0),
+        Orientation::Diagonal => (1, -1),
+        Orientation::AntiDiagonal => (1, 1),
+    }
+}
+
+type Line = (Orientation, i64);
+
+// inclusive range of positions along a line: x, or y on vertical lines
+type Range = (i64, i64);
+
+fn line_and_range(segment: &Segment) -> Option<(Line, Range)> {
+    let (x1, y1) = (segment.start.0 as i64, segment.start.1 as i64);
+    let (x2, y2) = (segment.end.0 as i64, segment.end.1 as i64);
+    let line = if y1 == y2 {
+        (Orientation::Horizontal, y1)
+    } else if x1 == x2 {
+        return Some(((Orientation::Vertical, x1), (y1.min(y2), y1.max(y2))));
+    } else if x2 - x1 == y2 - y1 {
+        (Orientation::Diagonal, x1 - y1)
+    } else if x2 - x1 == y1 - y2 {
+        (Orientation::AntiDiagonal, x1 + y1)
+    } else {
+        return None;
+    };
+    Some((line, (x1.min(x2), x1.max(x2))))
+}
+
+fn position_on(line: Line, point: (i64, i64)) -> i64 {
+    if line.0 == Orientation::Vertical {
+        point.1
+    } else {
+        point.0
+    }
+}
+
+// ranges covered at least once (merged) and at least twice along a line
+fn coverage(ranges: &[Range]) -> (Vec<Range>, Vec<Range>) {
+    let mut events: Vec<(i64, i32)> = ranges
+        .iter()
+        .flat_map(|&(lo, hi)| [(lo, 1), (hi + 1, -1)])
+        .collect();
+    events.sort_unstable();
+
+    let (mut covered, mut multi) = (vec![], vec![]);
+    let (mut covered_start, mut multi_start) = (0, 0);
+    let mut depth = 0;
+    let mut index = 0;
+    while index < events.len() {
+        let position = events[index].0;
+        let before = depth;
+        while index < events.len() && events[index].0 == position {
+            depth += events[index].1;
+            index += 1;
+        }
+        if before == 0 && depth > 0 {
+            covered_start = position;
+        }
+        if before > 0 && depth == 0 {
+            covered.push((covered_start, position - 1));
+        }
+        if before < 2 && depth >= 2 {
+            multi_start = position;
+        }
+        if before >= 2 && depth < 2 {
+            multi.push((multi_start, position - 1));
+        }
+    }
+    (covered, multi)
+}
+
+#[test]
+fn test_coverage() {
+    // GIVEN
+    let ranges = [(0, 5), (3, 8), (4, 4), (10, 12), (13, 14)];
+
+    // WHEN
+    let (covered, multi) = coverage(&ranges);
+
+    // THEN
+    assert_eq!(covered, vec![(0, 8), (10, 14)]);
+    assert_eq!(multi, vec![(3, 5)]);
+}
+
+// Every (x, y) where a "horizontal" (y, x range) meets a "vertical" (x, y range).
+// Horizontals sharing a y must not overlap.
+fn orthogonal_crossings(
+    horizontals: &[(i64, Range)],
+    verticals: &[(i64, Range)],
+) -> Vec<(i64, i64)> {
+    // at the same x, horizontals ending just before x leave first, then the
+    // ones starting at x enter, then verticals at x are queried
+    const LEAVE: u8 = 0;
+    const ENTER: u8 = 1;
+    const QUERY: u8 = 2;
+    let mut events: Vec<(i64, u8, usize)> = vec![];
+    for (index, &(_, (lo, hi))) in horizontals.iter().enumerate() {
+        events.push((lo, ENTER, index));
+        events.push((hi + 1, LEAVE, index));
+    }
+    for (index, &(x, _)) in verticals.iter().enumerate() {
+        events.push((x, QUERY, index));
+    }
+    events.sort_unstable();
+
+    let mut active = BTreeSet::new();
+    let mut crossings = vec![];
+    for (x, kind, index) in events {
+        match kind {
+            LEAVE => {
+                active.remove(&horizontals[index].0);
+            }
+            ENTER => {
+                active.insert(horizontals[index].0);
+            }
+            _ => {
+                let (lo, hi) = verticals[index].1;
+                crossings.extend(active.range(lo..=hi).map(|&y| (x, y)));
+            }
+        }
+    }
+    crossings
+}
+
+#[test]
+fn test_orthogonal_crossings() {
+    // GIVEN
+    let horizontals = [(4, (0, 5)), (6, (4, 9)), (4, (7, 9))];
+    let verticals = [(3, (2, 6)), (5, (4, 6)), (8, (0, 3)), (7, (4, 4))];
+
+    // WHEN
+    let mut crossings = orthogonal_crossings(&horizontals, &verticals);
+    crossings.sort_unstable();
+
+    // THEN
+    assert_eq!(crossings, vec![(3, 4), (5, 4), (5, 6), (7, 4)]);
+}
+
+// Range of keys of the `other` line for which the crossing with `line` falls
+// within `range`. Solving a1*x + b1*y = k1, a2*x + b2*y = k2 gives
+//   x * det = k1*b2 - k2*b1    y * det = a1*k2 - a2*k1
+// so the position along `line`, scaled by det, is `sign * other_key + offset`
+// with sign = +-1.
+fn crossing_keys(line: Line, range: Range, other: Orientation) -> Range {
+    let (a1, b1) = coefficients(line.0);
+    let (a2, b2) = coefficients(other);
+    let det = a1 * b2 - a2 * b1;
+    let (sign, offset) = if line.0 == Orientation::Vertical {
+        (a1, -a2 * line.1)
+    } else {
+        (-b1, line.1 * b2)
+    };
+    let first = sign * (range.0 * det - offset);
+    let second = sign * (range.1 * det - offset);
+    (first.min(second), first.max(second))
+}
+
+// crossing of two lines of different orientations, when it is a grid point
+fn crossing_point(first: Line, second: Line) -> Option<(i64, i64)> {
+    let (a1, b1) = coefficients(first.0);
+    let (a2, b2) = coefficients(second.0);
+    let det = a1 * b2 - a2 * b1;
+    let x = first.1 * b2 - second.1 * b1;
+    let y = a1 * second.1 - a2 * first.1;
+    if x % det != 0 || y % det != 0 {
+        return None;
+    }
+    Some((x / det, y / det))
+}
+
+fn contains(ranges: &[Range], position: i64) -> bool {
+    let index = ranges.partition_point(|&(_, hi)| hi < position);
+    index < ranges.len() && ranges[index].0 <= position
+}
+
+/// Number of grid points covered by at least two of the horizontal, vertical
+/// or 45 degrees `vents`; segments at any other angle are ignored.
+pub fn count_overlap_points(vents: &[&Segment]) -> u32 {
+    let mut ranges_per_line: HashMap<Line, Vec<Range>> = HashMap::new();
+    for (line, range) in vents.iter().filter_map(|seg| line_and_range(seg)) {
+        ranges_per_line.entry(line).or_default().push(range);
+    }
+
+    let mut covered_per_orientation: HashMap<Orientation, Vec<(i64, Vec<Range>)>> = HashMap::new();
+    let mut multi_per_line: HashMap<Line, Vec<Range>> = HashMap::new();
+    let mut count = 0;
+    for (line, ranges) in ranges_per_line {
+        let (covered, multi) = coverage(&ranges);
+        count += multi.iter().map(|(lo, hi)| hi - lo + 1).sum::<i64>();
+        covered_per_orientation
+            .entry(line.0)
+            .or_default()
+            .push((line.1, covered));
+        multi_per_line.insert(line, multi);
+    }
+
+    // points where lines of different orientations cross
+    let mut crossings = HashSet::new();
+    for (index, &first) in ORIENTATIONS.iter().enumerate() {
+        for &second in ORIENTATIONS[index + 1..].iter() {
+            let (Some(first_lines), Some(second_lines)) = (
+                covered_per_orientation.get(&first),
+                covered_per_orientation.get(&second),
+            ) else {
+                continue;
+            };
+            let horizontals: Vec<(i64, Range)> = first_lines
+                .iter()
+                .flat_map(|(key, covered)| {
+                    covered
+                        .iter()
+                        .map(move |&range| (*key, crossing_keys((first, *key), range, second)))
+                })
+                .collect();
+            let verticals: Vec<(i64, Range)> = second_lines
+                .iter()
+                .flat_map(|(key, covered)| {
+                    covered
+                        .iter()
+                        .map(move |&range| (*key, crossing_keys((second, *key), range, first)))
+                })
+                .collect();
+            for (second_key, first_key) in orthogonal_crossings(&horizontals, &verticals) {
+                if let Some(point) = crossing_point((first, first_key), (second, second_key)) {
+                    crossings.insert(point);
+                }
+            }
+        }
+    }
+
+    // crossings inside ranges covered twice were already counted, once per
+    // such line, while they must be counted exactly once
+    let lines_covered_twice = |point: (i64, i64)| {
+        ORIENTATIONS
+            .iter()
+            .filter(|&&orientation| {
+                let (a, b) = coefficients(orientation);
+                let line = (orientation, a * point.0 + b * point.1);
+                multi_per_line
+                    .get(&line)
+                    .is_some_and(|multi| contains(multi, position_on(line, point)))
+            })
+            .count() as i64
+    };
+    count += crossings
+        .into_iter()
+        .map(|point| 1 - lines_covered_twice(point))
+        .sum::<i64>();
+    count as u32
+}
+
+#[cfg(test)]
+fn segments(coordinates: &[(u32, u32, u32, u32)]) -> Vec<Segment> {
+    coordinates
+        .iter()
+        .map(|&(x1, y1, x2, y2)| crate::segment(x1, y1, x2, y2))
+        .collect()
+}
+
+#[test]
+fn test_count_overlap_points_tricky_cases() {
+    // GIVEN
+    let vents = segments(&[
+        // collinear overlaps, reversed, on every orientation
+        (0, 0, 4, 0),
+        (6, 0, 3, 0),
+        (9, 0, 9, 5),
+        (9, 3, 9, 1),
+        (0, 2, 3, 5),
+        (4, 6, 2, 4),
+        (10, 0, 6, 4),
+        (7, 3, 9, 1),
+        // three lines through (5, 5)
+        (5, 0, 5, 9),
+        (0, 5, 9, 5),
+        (3, 7, 7, 3),
+        // diagonal crossing between grid points
+        (0, 8, 1, 9),
+        (0, 9, 1, 8),
+        // a single point on a line, and off any line
+        (2, 0, 2, 0),
+        (20, 20, 20, 20),
+        // not 45 degrees: ignored
+        (0, 0, 4, 2),
+    ]);
+    let vents: Vec<&Segment> = vents.iter().collect();
+
+    // WHEN
+    let r = count_overlap_points(&vents);
+
+    // THEN
+    assert_eq!(r, crate::overlap::count_overlap_points_grid(&vents));
+}
+
+#[test]
+fn test_count_overlap_points_random_fields() {
+    // GIVEN
+    let mut seed = 12345u64;
+    let mut next = |bound: u64| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((seed >> 33) % bound) as u32
+    };
+    for _ in 0..50 {
+        let vents: Vec<Segment> = (0..60)
+            .map(|_| {
+                let (x1, y1, length) = (next(20), next(20), next(10));
+                let (x2, y2) = match next(4) {
+                    0 => (x1 + length, y1),
+                    1 => (x1, y1 + length),
+                    2 => (x1 + length, y1 + length),
+                    _ => (x1 + length, y1.saturating_sub(length)),
+                };
+                let (start, end) = if next(2) == 0 {
+                    ((x2, y2), (x1, y1))
+                } else {
+                    ((x1, y1), (x2, y2))
+                };
+                Segment { start, end }
+            })
+            .filter(|seg| line_and_range(seg).is_some())
+            .collect();
+        let vents: Vec<&Segment> = vents.iter().collect();
+
+        // WHEN
+        let r = count_overlap_points(&vents);
+
+        // THEN
+        assert_eq!(r, crate::overlap::count_overlap_points_grid(&vents));
+    }
+}
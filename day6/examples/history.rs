@@ -0,0 +1,33 @@
+// Exports the lanternfish counts of every day, to chart how the population
+// grows:
+//   cargo run -p day6 --example history -- day6/input.txt 256 csv > growth.csv
+//   cargo run -p day6 --example history -- day6/input.txt 256 json > growth.json
+
+use day6::{lanternfish_history, write_csv, write_json, Day6, Exact};
+
+fn main() -> std::io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let invalid = |message: String| std::io::Error::new(std::io::ErrorKind::InvalidInput, message);
+    let [path, days, format] = args.as_slice() else {
+        return Err(invalid(
+            "expected arguments: <input path | -> <days> <csv | json>".to_string(),
+        ));
+    };
+    let days: usize = days
+        .parse()
+        .map_err(|_| invalid(format!("invalid number of days '{}'", days)))?;
+    let input = aoc_common::read_input(path)?;
+    let ages = aoc_common::parse::<Day6>(&input).map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {}", path, err),
+        )
+    })?;
+    let history = lanternfish_history(&ages, &Exact).take(days + 1);
+    let mut out = std::io::stdout().lock();
+    match format.as_str() {
+        "csv" => write_csv(history, &mut out),
+        "json" => write_json(history, &mut out),
+        _ => Err(invalid(format!("unknown format '{}'", format))),
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{Approximate, PopulationModel};
+
+/// Long run behaviour of a population model.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GrowthAnalysis {
+    /// Factor the population is multiplied by every day in the long run: the
+    /// spectral radius of the transition matrix.
+    pub growth_factor: f64,
+    /// Days for the population to double in the long run, when it grows.
+    pub doubling_time: Option<f64>,
+    /// Share of the population in every bucket once growth settles, whatever
+    /// the starting fish; all zeros when every population dies out.
+    pub stable_distribution: Vec<f64>,
+}
+
+const TOLERANCE: f64 = 1e-13;
+const MAX_ITERATIONS: usize = 1_000_000;
+
+impl PopulationModel {
+    /// Growth factor, doubling time and stable distribution of the model,
+    /// from the dominant eigenvalue and eigenvector of its transition matrix.
+    pub fn growth_analysis(&self) -> GrowthAnalysis {
+        let matrix = self.transition_matrix(&Approximate);
+        let size = matrix.size();
+        // the spectral radius of a nonnegative matrix is 0 when its n-th
+        // power is, and then every population dies out within n days
+        let dies_out = matrix
+            .pow(size as u64, &Approximate)
+            .rows()
+            .iter()
+            .flatten()
+            .all(|&n| n == 0.0);
+        if dies_out {
+            return GrowthAnalysis {
+                growth_factor: 0.0,
+                doubling_time: None,
+                stable_distribution: vec![0.0; size],
+            };
+        }
+        // Power iteration on M + I: it has the same eigenvectors, and the
+        // added diagonal keeps the iteration from cycling on models where
+        // every fish spawns on the same days.
+        let step = |vector: &[f64]| -> Vec<f64> {
+            let moved = matrix.apply(vector, &Approximate);
+            moved.iter().zip(vector).map(|(m, v)| m + v).collect()
+        };
+        let mut vector = vec![1.0 / size as f64; size];
+        let mut factor = 1.0;
+        for _ in 0..MAX_ITERATIONS {
+            let next = step(&vector);
+            // vectors add up to 1, so the sum is the eigenvalue of M + I
+            let next_factor: f64 = next.iter().sum();
+            let next: Vec<f64> = next.iter().map(|v| v / next_factor).collect();
+            let change = next
+                .iter()
+                .zip(&vector)
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f64::max);
+            vector = next;
+            let settled = (next_factor - factor).abs() < TOLERANCE && change < TOLERANCE;
+            factor = next_factor;
+            if settled {
+                break;
+            }
+        }
+
+        let growth_factor = (factor - 1.0).max(0.0);
+        GrowthAnalysis {
+            growth_factor,
+            doubling_time: (growth_factor > 1.0).then(|| 2f64.ln() / growth_factor.ln()),
+            stable_distribution: vector,
+        }
+    }
+}
+
+#[test]
+fn test_lanternfish_growth_analysis() {
+    // GIVEN
+    let model = PopulationModel::LANTERNFISH;
+    let ages = [3u32, 4, 3, 1, 2];
+    let days: Vec<_> = model
+        .history(&ages, &Approximate)
+        .skip(3000)
+        .take(2)
+        .collect();
+
+    // WHEN
+    let analysis = model.growth_analysis();
+
+    // THEN
+    // a fish spawns after 7 days, and after 9 for newborns: x^9 = x^2 + 1
+    let x = analysis.growth_factor;
+    assert!((x.powi(9) - x.powi(2) - 1.0).abs() < 1e-9);
+    assert!((x - 1.0910).abs() < 1e-4);
+    let doubling_time = analysis.doubling_time.unwrap();
+    assert!((x.powf(doubling_time) - 2.0).abs() < 1e-9);
+
+    let simulated = days[1].total / days[0].total;
+    assert!((simulated - x).abs() < 1e-9);
+    assert!((analysis.stable_distribution.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    for (share, count) in analysis.stable_distribution.iter().zip(&days[1].counts) {
+        assert!((share - count / days[1].total).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_periodic_and_mortal_growth_analysis() {
+    // GIVEN
+    let doubling_every_other_day = PopulationModel {
+        cycle: 2,
+        maturation: 0,
+        offspring: 1,
+        lifespan: None,
+    };
+    let spawn_once = PopulationModel {
+        cycle: 2,
+        maturation: 1,
+        offspring: 1,
+        lifespan: Some(4),
+    };
+    let die_young = PopulationModel {
+        lifespan: Some(3),
+        ..spawn_once
+    };
+
+    // WHEN
+    let periodic = doubling_every_other_day.growth_analysis();
+    let stable = spawn_once.growth_analysis();
+    let dying = die_young.growth_analysis();
+
+    // THEN
+    assert!((periodic.growth_factor - 2f64.sqrt()).abs() < 1e-9);
+    assert!((periodic.doubling_time.unwrap() - 2.0).abs() < 1e-9);
+    assert!((stable.growth_factor - 1.0).abs() < 1e-9);
+    assert_eq!(stable.doubling_time, None);
+    assert!(dying.growth_factor.abs() < 1e-9);
+    assert_eq!(dying.doubling_time, None);
+}
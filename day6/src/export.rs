@@ -0,0 +1,101 @@
+use crate::DayCounts;
+use std::fmt::Display;
+use std::io::Write;
+
+fn join<T: Display>(counts: &[T], separator: &str) -> String {
+    counts
+        .iter()
+        .map(|count| count.to_string())
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+/// Writes day by day counts as CSV: a `day` column, one `bucket_<n>` column
+/// per bucket and a `total` column.
+pub fn write_csv<T: Display, W: Write>(
+    days: impl IntoIterator<Item = DayCounts<T>>,
+    out: &mut W,
+) -> std::io::Result<()> {
+    let mut days = days.into_iter().peekable();
+    let buckets = days.peek().map_or(0, |first| first.counts.len());
+    let columns: Vec<String> = (0..buckets).map(|n| format!(",bucket_{}", n)).collect();
+    writeln!(out, "day{},total", columns.concat())?;
+    for day in days {
+        writeln!(out, "{},{},{}", day.day, join(&day.counts, ","), day.total)?;
+    }
+    Ok(())
+}
+
+/// Writes day by day counts as a JSON array of
+/// `{"day": .., "counts": [..], "total": ..}` objects. Counts are written as
+/// plain numbers, however large.
+pub fn write_json<T: Display, W: Write>(
+    days: impl IntoIterator<Item = DayCounts<T>>,
+    out: &mut W,
+) -> std::io::Result<()> {
+    write!(out, "[")?;
+    for (index, day) in days.into_iter().enumerate() {
+        let separator = if index == 0 { "" } else { "," };
+        write!(
+            out,
+            "{}\n  {{\"day\": {}, \"counts\": [{}], \"total\": {}}}",
+            separator,
+            day.day,
+            join(&day.counts, ", "),
+            day.total
+        )?;
+    }
+    writeln!(out, "\n]")
+}
+
+#[cfg(test)]
+fn example_days() -> Vec<DayCounts<u64>> {
+    crate::PopulationModel::LANTERNFISH
+        .history(&[3, 4, 3, 1, 2], &crate::Modulo(u64::MAX))
+        .take(3)
+        .collect()
+}
+
+#[test]
+fn test_write_csv() {
+    // GIVEN
+    let days = example_days();
+
+    // WHEN
+    let mut out = vec![];
+    write_csv(days, &mut out).unwrap();
+
+    // THEN
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "day,bucket_0,bucket_1,bucket_2,bucket_3,bucket_4,bucket_5,bucket_6,bucket_7,bucket_8,total
+0,0,1,1,2,1,0,0,0,0,5
+1,1,1,2,1,0,0,0,0,0,5
+2,1,2,1,0,0,0,1,0,1,6
+"
+    );
+}
+
+#[test]
+fn test_write_json() {
+    // GIVEN
+    let days = example_days();
+
+    // WHEN
+    let mut out = vec![];
+    write_json(days, &mut out).unwrap();
+    let mut empty = vec![];
+    write_json(Vec::<DayCounts<u64>>::new(), &mut empty).unwrap();
+
+    // THEN
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        r#"[
+  {"day": 0, "counts": [0, 1, 1, 2, 1, 0, 0, 0, 0], "total": 5},
+  {"day": 1, "counts": [1, 1, 2, 1, 0, 0, 0, 0, 0], "total": 5},
+  {"day": 2, "counts": [1, 2, 1, 0, 0, 0, 1, 0, 1], "total": 6}
+]
+"#
+    );
+    assert_eq!(String::from_utf8(empty).unwrap(), "[\n]\n");
+}
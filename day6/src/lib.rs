@@ -0,0 +1,269 @@
+use aoc_common::{parse_token, ParseError, Solution};
+use num_bigint::BigUint;
+use std::fmt;
+
+mod analysis;
+mod export;
+mod matrix;
+mod model;
+
+pub use analysis::GrowthAnalysis;
+pub use export::{write_csv, write_json};
+pub use matrix::{Approximate, Counting, Exact, Matrix, Modulo};
+pub use model::{DayCounts, History, PopulationModel};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<u32>;
+    type Answer1 = BigUint;
+    type Answer2 = BigUint;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(ages: &Vec<u32>) -> BigUint {
+        compute_part1(ages)
+    }
+
+    fn part2(ages: &Vec<u32>) -> BigUint {
+        compute_part2(ages)
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    const EXPECTED: &str = "a lanternfish age from 0 to 8";
+    input
+        .split(",")
+        .map(|f| match parse_token(1, input, f, EXPECTED)? {
+            age if age <= 8 => Ok(age),
+            _ => Err(ParseError::at(1, input, f, EXPECTED)),
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_reports_malformed_age() {
+    // GIVEN
+    let input = "3,4,3,one,2";
+
+    // WHEN
+    let r = parse_input(input);
+
+    // THEN
+    let err = r.unwrap_err();
+    assert_eq!((err.line, err.column), (1, 7));
+    assert_eq!(err.token, "one");
+}
+
+#[test]
+fn test_parse_reports_age_out_of_range() {
+    // GIVEN
+    let input = "3,4,9";
+
+    // WHEN
+    let r = aoc_common::solve::<Day6>(input);
+
+    // THEN
+    let err = r.unwrap_err();
+    assert_eq!((err.line, err.column), (1, 5));
+    assert_eq!(err.token, "9");
+    assert_eq!(err.expected, "a lanternfish age from 0 to 8");
+}
+
+#[test]
+fn test_parse_given_example_input() {
+    // GIVEN
+    let input = "3,4,3,1,2";
+
+    // WHEN
+    let ages = parse_input(input).unwrap();
+
+    // THEN
+    assert!(ages.len() == 5);
+    assert!(ages == vec![3, 4, 3, 1, 2]);
+}
+
+/// Number of lanternfish after `days`, simulated day by day in the given
+/// arithmetic; with `Exact` the count never overflows.
+pub fn grow_lanternfish<C: Counting>(ages: &[u32], days: u32, counting: &C) -> C::Count {
+    PopulationModel::LANTERNFISH.grow(ages, days, counting)
+}
+
+/// The lanternfish counts of every day, starting with the given fish on day 0.
+pub fn lanternfish_history<'a, C: Counting>(ages: &[u32], counting: &'a C) -> History<'a, C> {
+    PopulationModel::LANTERNFISH.history(ages, counting)
+}
+
+/// First day on which there are more than `threshold` lanternfish, in
+/// O(log day) steps (see [`PopulationModel::first_day_over`]).
+pub fn first_day_over(ages: &[u32], threshold: &BigUint) -> Option<u64> {
+    PopulationModel::LANTERNFISH.first_day_over(ages, threshold)
+}
+
+/// The lanternfish count stopped fitting in a `u64`.
+#[derive(Debug, PartialEq)]
+pub struct Overflow {
+    /// First day on which the population is larger than `u64::MAX`.
+    pub day: u32,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the lanternfish count overflows u64 on day {}", self.day)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// Number of lanternfish after `days` as a `u64`, or the first day on which
+/// the population no longer fits.
+///
+/// Panics if an age is above 8, which the puzzle input never has.
+pub fn grow_lanternfish_checked(ages: &[u32], days: u32) -> Result<u64, Overflow> {
+    let mut ages_count = [0u64; 9];
+    for &fish in ages.iter() {
+        ages_count[fish as usize] += 1;
+    }
+    let mut population = ages.len() as u64;
+    for day in 1..=days {
+        let about_to_create_new_fish = ages_count[0];
+        // no bucket is larger than the whole population
+        population = population
+            .checked_add(about_to_create_new_fish)
+            .ok_or(Overflow { day })?;
+        ages_count.rotate_left(1);
+        ages_count[6] += about_to_create_new_fish;
+    }
+    Ok(population)
+}
+
+fn compute_part1(ages: &[u32]) -> BigUint {
+    grow_lanternfish(ages, 80, &Exact)
+}
+
+fn compute_part2(ages: &[u32]) -> BigUint {
+    grow_lanternfish(ages, 256, &Exact)
+}
+
+#[test]
+fn test_grow_lanternfish() {
+    // GIVEN
+    let ages = vec![3u32, 4, 3, 1, 2];
+
+    // WHEN
+    let r = grow_lanternfish(&ages, 18, &Exact);
+
+    // THEN
+    assert!(r == BigUint::from(26u32));
+}
+
+#[test]
+fn test_grow_lanternfish_checked() {
+    // GIVEN
+    let ages = vec![3u32, 4, 3, 1, 2];
+
+    // WHEN
+    let r = grow_lanternfish_checked(&ages, 256);
+    let overflow = grow_lanternfish_checked(&ages, 1000);
+
+    // THEN
+    assert_eq!(r, Ok(26984457539));
+    let day = overflow.unwrap_err().day;
+    assert!(grow_lanternfish(&ages, day - 1, &Exact) <= BigUint::from(u64::MAX));
+    assert!(grow_lanternfish(&ages, day, &Exact) > BigUint::from(u64::MAX));
+    assert_eq!(
+        Overflow { day }.to_string(),
+        format!("the lanternfish count overflows u64 on day {}", day)
+    );
+}
+
+/// Number of lanternfish after `days`, in the given arithmetic, in O(log days)
+/// steps (see [`PopulationModel::after_days`]).
+pub fn fish_after_days<C: Counting>(ages: &[u32], days: u64, counting: &C) -> C::Count {
+    PopulationModel::LANTERNFISH.after_days(ages, days, counting)
+}
+
+#[test]
+fn test_fish_after_days_matches_day_by_day_growth() {
+    // GIVEN
+    let ages = vec![3u32, 4, 3, 1, 2];
+
+    // WHEN
+    let exact = fish_after_days(&ages, 256, &Exact);
+    let modulo = fish_after_days(&ages, 256, &Modulo(1_000_000_007));
+
+    // THEN
+    assert_eq!(exact, BigUint::from(26984457539u64));
+    assert_eq!(modulo, 26984457539 % 1_000_000_007);
+    assert_eq!(fish_after_days(&ages, 18, &Modulo(u64::MAX)), 26);
+    assert_eq!(fish_after_days(&ages, 0, &Exact), 5u32.into());
+}
+
+#[test]
+fn test_fish_after_days_far_in_the_future() {
+    // GIVEN
+    let ages = vec![3u32, 4, 3, 1, 2];
+    let prime = 998_244_353;
+
+    // WHEN
+    let exact = fish_after_days(&ages, 5000, &Exact);
+    let modulo = fish_after_days(&ages, 5000, &Modulo(prime));
+    let trillion_days = fish_after_days(&ages, 1_000_000_000_000, &Modulo(prime));
+
+    // THEN
+    assert_eq!(exact % prime, modulo.into());
+    // a trillion days are a million days, a million times over
+    let model = PopulationModel::LANTERNFISH;
+    let counting = Modulo(prime);
+    let million_days = model.transition_matrix(&counting).pow(1_000_000, &counting);
+    let counts = million_days
+        .pow(1_000_000, &counting)
+        .apply(&model.initial_counts(&ages, &counting), &counting);
+    let expected = counts
+        .iter()
+        .fold(0, |sum, count| counting.add(&sum, count));
+    assert_eq!(trillion_days, expected);
+}
+
+#[test]
+fn part_1_given_example() {
+    // GIVEN
+    let input = "3,4,3,1,2";
+
+    // WHEN
+    let ages = parse_input(input).unwrap();
+    let r = compute_part1(&ages);
+
+    // THEN
+    assert!(r == BigUint::from(5934u32));
+}
+
+#[test]
+fn part_2_given_example() {
+    // GIVEN
+    let input = "3,4,3,1,2";
+
+    // WHEN
+    let ages = parse_input(input).unwrap();
+    let r = compute_part2(&ages);
+
+    // THEN
+    assert!(r == BigUint::from(26984457539u64));
+}
+
+#[test]
+fn test_solve_tolerates_crlf_bom_and_trailing_newlines() {
+    // GIVEN
+    let input = "\u{feff}3,4,3,1,2\r\n";
+
+    // WHEN
+    let r = aoc_common::solve::<Day6>(input);
+
+    // THEN
+    assert_eq!(
+        r,
+        Ok((BigUint::from(5934u32), BigUint::from(26984457539u64)))
+    );
+}
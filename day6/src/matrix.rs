@@ -0,0 +1,174 @@
+use num_bigint::BigUint;
+
+/// The arithmetic population counts are carried out in: modulo some number,
+/// or exact.
+pub trait Counting {
+    type Count: Clone;
+
+    fn count(&self, n: u64) -> Self::Count;
+    fn add(&self, a: &Self::Count, b: &Self::Count) -> Self::Count;
+    fn mul(&self, a: &Self::Count, b: &Self::Count) -> Self::Count;
+}
+
+/// Counts modulo a number, usually a large prime, for populations too large
+/// to be written down. The modulus must not be zero.
+pub struct Modulo(pub u64);
+
+impl Counting for Modulo {
+    type Count = u64;
+
+    fn count(&self, n: u64) -> u64 {
+        n % self.0
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 + *b as u128) % self.0 as u128) as u64
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 * *b as u128) % self.0 as u128) as u64
+    }
+}
+
+/// Exact counts, as big as they get.
+pub struct Exact;
+
+impl Counting for Exact {
+    type Count = BigUint;
+
+    fn count(&self, n: u64) -> BigUint {
+        BigUint::from(n)
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a + b
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b
+    }
+}
+
+/// Floating point counts, for growth rates rather than populations.
+pub struct Approximate;
+
+impl Counting for Approximate {
+    type Count = f64;
+
+    fn count(&self, n: u64) -> f64 {
+        n as f64
+    }
+
+    fn add(&self, a: &f64, b: &f64) -> f64 {
+        a + b
+    }
+
+    fn mul(&self, a: &f64, b: &f64) -> f64 {
+        a * b
+    }
+}
+
+/// A square matrix of counts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix<T> {
+    rows: Vec<Vec<T>>,
+}
+
+impl<T: Clone> Matrix<T> {
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Matrix<T> {
+        assert!(
+            rows.iter().all(|row| row.len() == rows.len()),
+            "matrix must be square"
+        );
+        Matrix { rows }
+    }
+
+    pub fn size(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn rows(&self) -> &[Vec<T>] {
+        &self.rows
+    }
+
+    pub fn identity<C: Counting<Count = T>>(size: usize, counting: &C) -> Matrix<T> {
+        Matrix {
+            rows: (0..size)
+                .map(|i| (0..size).map(|j| counting.count((i == j) as u64)).collect())
+                .collect(),
+        }
+    }
+
+    pub fn multiply<C: Counting<Count = T>>(&self, other: &Matrix<T>, counting: &C) -> Matrix<T> {
+        let size = self.size();
+        let rows = (0..size)
+            .map(|i| {
+                (0..size)
+                    .map(|j| {
+                        (0..size).fold(counting.count(0), |sum, k| {
+                            counting.add(&sum, &counting.mul(&self.rows[i][k], &other.rows[k][j]))
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        Matrix { rows }
+    }
+
+    /// The matrix raised to `exponent`, by repeated squaring: only
+    /// O(log exponent) multiplications.
+    pub fn pow<C: Counting<Count = T>>(&self, exponent: u64, counting: &C) -> Matrix<T> {
+        let mut result = Matrix::identity(self.size(), counting);
+        let mut square = self.clone();
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.multiply(&square, counting);
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                square = square.multiply(&square, counting);
+            }
+        }
+        result
+    }
+
+    pub fn apply<C: Counting<Count = T>>(&self, vector: &[T], counting: &C) -> Vec<T> {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(vector)
+                    .fold(counting.count(0), |sum, (a, b)| {
+                        counting.add(&sum, &counting.mul(a, b))
+                    })
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_matrix_pow() {
+    // GIVEN
+    let fibonacci = Matrix::from_rows(vec![vec![1u64, 1], vec![1, 0]]);
+
+    // WHEN
+    let exact = Matrix::from_rows(vec![
+        vec![BigUint::from(1u32), BigUint::from(1u32)],
+        vec![BigUint::from(1u32), BigUint::from(0u32)],
+    ])
+    .pow(100, &Exact);
+    let modulo = fibonacci.pow(100, &Modulo(1_000_000_007));
+
+    // THEN
+    let fib_100: BigUint = "354224848179261915075".parse().unwrap();
+    assert_eq!(exact.rows()[0][1], fib_100);
+    assert_eq!(
+        BigUint::from(modulo.rows()[0][1]),
+        fib_100 % 1_000_000_007u32
+    );
+    assert_eq!(
+        fibonacci.pow(0, &Modulo(7)),
+        Matrix::identity(2, &Modulo(7))
+    );
+}
@@ -0,0 +1,458 @@
+use crate::{Counting, Exact, Matrix};
+use num_bigint::BigUint;
+use std::collections::HashSet;
+
+/// How a population of fish grows: every fish spawns `offspring` new fish
+/// once every `cycle` days, newborns need `maturation` more days before their
+/// first cycle starts and, with a `lifespan`, fish die on reaching that age.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PopulationModel {
+    pub cycle: u32,
+    pub maturation: u32,
+    pub offspring: u64,
+    pub lifespan: Option<u32>,
+}
+
+impl PopulationModel {
+    /// The puzzle's lanternfish: a 7 days cycle, 2 more days for newborns, a
+    /// single offspring and no death.
+    pub const LANTERNFISH: PopulationModel = PopulationModel {
+        cycle: 7,
+        maturation: 2,
+        offspring: 1,
+        lifespan: None,
+    };
+
+    // age of a fish on the day it first spawns, and timer of newborns + 1
+    fn first_spawn(&self) -> u32 {
+        self.cycle + self.maturation
+    }
+
+    /// Number of counts the population is split into: fish are counted per
+    /// timer value, or per age when they can die.
+    pub fn buckets(&self) -> usize {
+        match self.lifespan {
+            None => self.first_spawn() as usize,
+            Some(lifespan) => lifespan as usize,
+        }
+    }
+
+    /// Counts per bucket for fish with the given timers. When their age
+    /// matters, fish are taken to be in their first cycle: the youngest they
+    /// can be with such a timer.
+    ///
+    /// Panics unless the cycle lasts at least a day and every timer is
+    /// shorter than a newborn's: callers check their timers first, as the
+    /// puzzle parser does with ages from 0 to 8. The methods below start
+    /// from these counts, and panic likewise.
+    pub fn initial_counts<C: Counting>(&self, timers: &[u32], counting: &C) -> Vec<C::Count> {
+        assert!(self.cycle > 0, "the cycle must last at least one day");
+        let mut counts = vec![counting.count(0); self.buckets()];
+        for &timer in timers.iter() {
+            assert!(
+                timer < self.first_spawn(),
+                "timer {} is longer than a newborn's",
+                timer
+            );
+            let bucket = match self.lifespan {
+                None => timer,
+                Some(_) => self.first_spawn() - 1 - timer,
+            };
+            // fish already past their lifespan are dead
+            if let Some(count) = counts.get_mut(bucket as usize) {
+                *count = counting.add(count, &counting.count(1));
+            }
+        }
+        counts
+    }
+
+    /// Moves the counts one day forward.
+    pub fn step<C: Counting>(&self, counts: &mut [C::Count], counting: &C) {
+        let offspring = counting.count(self.offspring);
+        match self.lifespan {
+            None => {
+                let about_to_create_new_fish = counts[0].clone();
+                counts.rotate_left(1);
+                let newborns = counts.len() - 1;
+                counts[newborns] = counting.mul(&about_to_create_new_fish, &offspring);
+                let parents = self.cycle as usize - 1;
+                counts[parents] = counting.add(&counts[parents], &about_to_create_new_fish);
+            }
+            Some(_) if counts.is_empty() => {}
+            Some(_) => {
+                // everyone gets a day older, the oldest ones die
+                counts.rotate_right(1);
+                let (first_spawn, cycle) = (self.first_spawn() as usize, self.cycle as usize);
+                let parents = counts
+                    .iter()
+                    .enumerate()
+                    .filter(|&(age, _)| age >= first_spawn && (age - first_spawn) % cycle == 0)
+                    .fold(counting.count(0), |sum, (_, count)| {
+                        counting.add(&sum, count)
+                    });
+                counts[0] = counting.mul(&parents, &offspring);
+            }
+        }
+    }
+
+    /// Tomorrow's counts are this matrix times today's.
+    pub fn transition_matrix<C: Counting>(&self, counting: &C) -> Matrix<C::Count> {
+        let size = self.buckets();
+        // column j is where the fish of bucket j end up
+        let columns: Vec<Vec<C::Count>> = (0..size)
+            .map(|j| {
+                let mut counts: Vec<C::Count> =
+                    (0..size).map(|i| counting.count((i == j) as u64)).collect();
+                self.step(&mut counts, counting);
+                counts
+            })
+            .collect();
+        Matrix::from_rows(
+            (0..size)
+                .map(|i| columns.iter().map(|column| column[i].clone()).collect())
+                .collect(),
+        )
+    }
+
+    /// Number of fish after `days`, simulated day by day.
+    pub fn grow<C: Counting>(&self, timers: &[u32], days: u32, counting: &C) -> C::Count {
+        let mut counts = self.initial_counts(timers, counting);
+        for _ in 0..days {
+            self.step(&mut counts, counting);
+        }
+        total(&counts, counting)
+    }
+
+    /// Number of fish after `days`. The daily transition is raised to the
+    /// power `days` by repeated squaring, so this only takes O(log days)
+    /// steps: with `Modulo`, any number of days is within reach.
+    pub fn after_days<C: Counting>(&self, timers: &[u32], days: u64, counting: &C) -> C::Count {
+        let counts = self.initial_counts(timers, counting);
+        let counts = self
+            .transition_matrix(counting)
+            .pow(days, counting)
+            .apply(&counts, counting);
+        total(&counts, counting)
+    }
+
+    /// The counts of every day, starting with the given fish on day 0.
+    pub fn history<'a, C: Counting>(&'a self, timers: &[u32], counting: &'a C) -> History<'a, C> {
+        History {
+            model: self,
+            counting,
+            day: 0,
+            counts: self.initial_counts(timers, counting),
+        }
+    }
+
+    /// First day on which there are more than `threshold` fish, or `None` if
+    /// that never happens. The transition is fast-forwarded by powers of two
+    /// days until the population is over the threshold, then the day is
+    /// narrowed down by halving the jumps, so this takes O(log day) steps
+    /// even for astronomically large thresholds.
+    ///
+    /// Populations with a lifespan can shrink, so they are stepped one day at
+    /// a time instead, until they go over the threshold or come back to counts
+    /// already seen.
+    pub fn first_day_over(&self, timers: &[u32], threshold: &BigUint) -> Option<u64> {
+        let counts = self.initial_counts(timers, &Exact);
+        if total(&counts, &Exact) > *threshold {
+            return Some(0);
+        }
+        if self.lifespan.is_some() {
+            return self.first_day_over_stepping(counts, threshold);
+        }
+        if self.offspring == 0 || timers.is_empty() {
+            // the population never grows
+            return None;
+        }
+
+        // jumps[i] moves 2^i days forward; find the first one going over
+        let mut jumps = vec![self.transition_matrix(&Exact)];
+        while total(&jumps[jumps.len() - 1].apply(&counts, &Exact), &Exact) <= *threshold {
+            let last = &jumps[jumps.len() - 1];
+            jumps.push(last.multiply(last, &Exact));
+        }
+
+        // latest day still at or under the threshold
+        let (mut day, mut counts) = (0u64, counts);
+        for (i, jump) in jumps.iter().enumerate().rev() {
+            let next = jump.apply(&counts, &Exact);
+            if total(&next, &Exact) <= *threshold {
+                day += 1 << i;
+                counts = next;
+            }
+        }
+        Some(day + 1)
+    }
+
+    // first day over the threshold, one day at a time; under the threshold
+    // there are finitely many counts, so they either go over or repeat
+    fn first_day_over_stepping(
+        &self,
+        mut counts: Vec<BigUint>,
+        threshold: &BigUint,
+    ) -> Option<u64> {
+        let mut seen = HashSet::new();
+        for day in 1.. {
+            seen.insert(counts.clone());
+            self.step(&mut counts, &Exact);
+            if total(&counts, &Exact) > *threshold {
+                return Some(day);
+            }
+            if seen.contains(&counts) {
+                return None;
+            }
+        }
+        unreachable!()
+    }
+}
+
+/// The fish counts on one day, per bucket (see [`PopulationModel::buckets`])
+/// and in total.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayCounts<T> {
+    pub day: u32,
+    pub counts: Vec<T>,
+    pub total: T,
+}
+
+/// Day after day counts of a population, see [`PopulationModel::history`].
+pub struct History<'a, C: Counting> {
+    model: &'a PopulationModel,
+    counting: &'a C,
+    day: u32,
+    counts: Vec<C::Count>,
+}
+
+impl<C: Counting> Iterator for History<'_, C> {
+    type Item = DayCounts<C::Count>;
+
+    fn next(&mut self) -> Option<DayCounts<C::Count>> {
+        let day_counts = DayCounts {
+            day: self.day,
+            counts: self.counts.clone(),
+            total: total(&self.counts, self.counting),
+        };
+        self.model.step(&mut self.counts, self.counting);
+        self.day += 1;
+        Some(day_counts)
+    }
+}
+
+pub(crate) fn total<C: Counting>(counts: &[C::Count], counting: &C) -> C::Count {
+    counts
+        .iter()
+        .fold(counting.count(0), |sum, count| counting.add(&sum, count))
+}
+
+#[cfg(test)]
+use crate::Modulo;
+
+#[test]
+#[should_panic]
+fn test_initial_counts_rejects_timers_longer_than_a_newborns() {
+    PopulationModel::LANTERNFISH.initial_counts(&[3, 4, 9], &Exact);
+}
+
+#[test]
+fn test_lanternfish_transition_matrix() {
+    // GIVEN
+    let mut expected = vec![vec![0u64; 9]; 9];
+    for (timer, row) in expected.iter_mut().take(8).enumerate() {
+        row[timer + 1] = 1;
+    }
+    expected[6][0] = 1;
+    expected[8][0] = 1;
+
+    // WHEN
+    let matrix = PopulationModel::LANTERNFISH.transition_matrix(&Modulo(u64::MAX));
+
+    // THEN
+    assert_eq!(matrix, Matrix::from_rows(expected));
+}
+
+#[test]
+fn test_more_offspring_and_shorter_cycle() {
+    // GIVEN
+    let model = PopulationModel {
+        cycle: 2,
+        maturation: 1,
+        offspring: 2,
+        lifespan: None,
+    };
+
+    // WHEN
+    let r: Vec<u64> = (0..=4)
+        .map(|days| model.grow(&[0], days, &Modulo(u64::MAX)))
+        .collect();
+
+    // THEN
+    assert_eq!(r, vec![1, 3, 3, 5, 9]);
+    assert_eq!(model.after_days(&[0], 4, &Modulo(u64::MAX)), 9);
+}
+
+#[test]
+fn test_no_maturation_delay() {
+    // GIVEN
+    let model = PopulationModel {
+        cycle: 1,
+        maturation: 0,
+        offspring: 1,
+        lifespan: None,
+    };
+
+    // WHEN
+    let r = model.grow(&[0, 0, 0], 10, &Modulo(u64::MAX));
+
+    // THEN
+    assert_eq!(r, 3 << 10);
+}
+
+#[test]
+fn test_mortality() {
+    // GIVEN
+    let spawn_once = PopulationModel {
+        cycle: 2,
+        maturation: 1,
+        offspring: 1,
+        lifespan: Some(4),
+    };
+    let die_young = PopulationModel {
+        lifespan: Some(3),
+        ..spawn_once
+    };
+
+    // WHEN
+    let r: Vec<u64> = (0..=4)
+        .map(|days| spawn_once.grow(&[0], days, &Modulo(u64::MAX)))
+        .collect();
+
+    // THEN
+    assert_eq!(r, vec![1, 2, 1, 1, 2]);
+    assert_eq!(spawn_once.after_days(&[0], 4, &Modulo(u64::MAX)), 2);
+    assert_eq!(die_young.grow(&[2, 1, 0], 3, &Modulo(u64::MAX)), 0);
+}
+
+#[test]
+fn test_long_lifespan_changes_nothing_yet() {
+    // GIVEN
+    let ages = [3u32, 4, 3, 1, 2];
+    let mortal = PopulationModel {
+        lifespan: Some(100),
+        ..PopulationModel::LANTERNFISH
+    };
+
+    // WHEN
+    let r = mortal.grow(&ages, 80, &Modulo(u64::MAX));
+    let fast = mortal.after_days(&ages, 80, &Modulo(u64::MAX));
+
+    // THEN
+    assert_eq!(r, 5934);
+    assert_eq!(fast, 5934);
+}
+
+#[test]
+fn test_history() {
+    // GIVEN
+    let ages = [3u32, 4, 3, 1, 2];
+
+    // WHEN
+    let days: Vec<DayCounts<u64>> = PopulationModel::LANTERNFISH
+        .history(&ages, &Modulo(u64::MAX))
+        .take(19)
+        .collect();
+
+    // THEN
+    assert_eq!(days[0].counts, vec![0, 1, 1, 2, 1, 0, 0, 0, 0]);
+    assert_eq!(days[1].counts, vec![1, 1, 2, 1, 0, 0, 0, 0, 0]);
+    assert_eq!(days[2].counts, vec![1, 2, 1, 0, 0, 0, 1, 0, 1]);
+    assert_eq!((days[2].day, days[2].total), (2, 6));
+    assert_eq!((days[18].day, days[18].total), (18, 26));
+}
+
+#[test]
+fn test_first_day_over() {
+    // GIVEN
+    let ages = [3u32, 4, 3, 1, 2];
+    let model = PopulationModel::LANTERNFISH;
+    let totals: Vec<u64> = model
+        .history(&ages, &Modulo(u64::MAX))
+        .take(300)
+        .map(|day| day.total)
+        .collect();
+
+    // WHEN
+    let first_day_over = |threshold: u64| model.first_day_over(&ages, &BigUint::from(threshold));
+
+    // THEN
+    let thresholds = (0..100).map(|n| 1.27f64.powi(n) as u64);
+    for threshold in thresholds.chain([4, 5, 6, 26, 5933, 5934]) {
+        let expected = totals.iter().position(|&total| total > threshold);
+        assert_eq!(first_day_over(threshold), expected.map(|day| day as u64));
+    }
+    assert_eq!(first_day_over(26984457538), Some(256));
+    assert_eq!(first_day_over(26984457539), Some(257));
+}
+
+#[test]
+fn test_first_day_over_huge_threshold() {
+    // GIVEN
+    let ages = [3u32, 4, 3, 1, 2];
+    let threshold = BigUint::from(10u32).pow(1000);
+
+    // WHEN
+    let day = PopulationModel::LANTERNFISH.first_day_over(&ages, &threshold);
+
+    // THEN
+    let day = day.unwrap();
+    assert!(PopulationModel::LANTERNFISH.after_days(&ages, day, &Exact) > threshold);
+    assert!(PopulationModel::LANTERNFISH.after_days(&ages, day - 1, &Exact) <= threshold);
+}
+
+#[test]
+fn test_first_day_over_with_lifespan() {
+    // GIVEN fish dying of old age, before or after spawning
+    let mortal = PopulationModel {
+        lifespan: Some(20),
+        ..PopulationModel::LANTERNFISH
+    };
+    let short_lived = PopulationModel {
+        lifespan: Some(9),
+        ..PopulationModel::LANTERNFISH
+    };
+    let ages = [3, 4];
+
+    // WHEN
+    let first_day_over = |model: &PopulationModel, threshold: u32| {
+        model.first_day_over(&ages, &BigUint::from(threshold))
+    };
+
+    // THEN
+    let expected = mortal
+        .history(&ages, &Exact)
+        .find(|counts| counts.total > 100u32.into())
+        .map(|counts| counts.day as u64);
+    assert_eq!(first_day_over(&mortal, 100), expected);
+    assert_eq!(first_day_over(&short_lived, 2), None);
+    assert_eq!(first_day_over(&short_lived, 1), Some(0));
+}
+
+#[test]
+fn test_first_day_over_without_growth() {
+    // GIVEN
+    let barren = PopulationModel {
+        offspring: 0,
+        ..PopulationModel::LANTERNFISH
+    };
+
+    // WHEN
+    let r = barren.first_day_over(&[3, 4], &BigUint::from(2u32));
+    let empty = PopulationModel::LANTERNFISH.first_day_over(&[], &BigUint::from(0u32));
+    let already_over = barren.first_day_over(&[3, 4], &BigUint::from(1u32));
+
+    // THEN
+    assert_eq!(r, None);
+    assert_eq!(empty, None);
+    assert_eq!(already_over, Some(0));
+}
@@ -0,0 +1,38 @@
+// Exports the total fuel for the crabs to line up on every position, to plot
+// the cost landscape, and prints the cheapest positions:
+//   cargo run -p day7 --example fuel_curve -- day7/input.txt linear > part1.csv
+//   cargo run -p day7 --example fuel_curve -- day7/input.txt triangular > part2.csv
+
+use day7::{align, write_fuel_csv, CostFunction, Crab, Day7, Linear, Triangular};
+
+fn main() -> std::io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let invalid = |message: String| std::io::Error::new(std::io::ErrorKind::InvalidInput, message);
+    let [path, cost] = args.as_slice() else {
+        return Err(invalid(
+            "expected arguments: <input path | -> <linear | triangular>".to_string(),
+        ));
+    };
+    let cost: &dyn CostFunction = match cost.as_str() {
+        "linear" => &Linear,
+        "triangular" => &Triangular,
+        _ => return Err(invalid(format!("unknown cost '{}'", cost))),
+    };
+    let input = aoc_common::read_input(path)?;
+    let positions = aoc_common::parse::<Day7>(&input).map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: {}", path, err),
+        )
+    })?;
+    let crabs: Vec<Crab> = positions.into_iter().map(Crab::at).collect();
+    if let Some(alignment) = align(&crabs, cost) {
+        eprintln!(
+            "cheapest positions {} to {}, for {} fuel",
+            alignment.positions.start(),
+            alignment.positions.end(),
+            alignment.fuel
+        );
+    }
+    write_fuel_csv(&crabs, cost, &mut std::io::stdout().lock())
+}
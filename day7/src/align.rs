@@ -0,0 +1,229 @@
+use crate::CostFunction;
+use std::io::Write;
+use std::ops::RangeInclusive;
+
+/// A crab at some position, standing for `weight` crabs there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crab {
+    pub position: u32,
+    pub weight: u64,
+}
+
+impl Crab {
+    /// A single crab at `position`.
+    pub fn at(position: u32) -> Crab {
+        Crab {
+            position,
+            weight: 1,
+        }
+    }
+}
+
+/// Where crabs can line up for the least fuel, and how much fuel that takes.
+/// The cheapest positions are all next to each other, from the first to the
+/// last of `positions`: a single one unless there are ties.
+#[derive(Debug, PartialEq)]
+pub struct Alignment {
+    pub positions: RangeInclusive<u32>,
+    pub fuel: u128,
+}
+
+/// Total fuel for all `crabs` to move to `target`.
+pub fn total_fuel<C: CostFunction + ?Sized>(crabs: &[Crab], cost: &C, target: u32) -> u128 {
+    crabs
+        .iter()
+        .map(|crab| crab.weight as u128 * cost.fuel(crab.position.abs_diff(target)))
+        .sum()
+}
+
+/// Cheapest positions for all `crabs` to line up on, for any convex cost, or
+/// `None` without crabs. Ties are only looked for between the outermost crabs.
+///
+/// The total fuel is then convex in the target too: the extra fuel of moving
+/// the target one more position to the right only grows. This is a ternary
+/// search on the integers, done as a binary search for the first position
+/// where moving right stops paying off, in O(n log(window)), then for the
+/// first one where it costs more.
+pub fn align<C: CostFunction + ?Sized>(crabs: &[Crab], cost: &C) -> Option<Alignment> {
+    if crabs.is_empty() {
+        return None;
+    }
+    let (mut low, mut high) = cost.search_window(crabs);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if total_fuel(crabs, cost, middle + 1) >= total_fuel(crabs, cost, middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    let first = low;
+    let fuel = total_fuel(crabs, cost, first);
+    let (_, mut high) = crate::cost::outermost(crabs);
+    while low < high {
+        let middle = low + (high - low).div_ceil(2);
+        if total_fuel(crabs, cost, middle) == fuel {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    Some(Alignment {
+        positions: first..=low,
+        fuel,
+    })
+}
+
+/// Total fuel for all `crabs` to move to every position between the
+/// outermost ones, to see how it changes around the cheapest positions.
+pub fn fuel_curve<'a, C: CostFunction + ?Sized>(
+    crabs: &'a [Crab],
+    cost: &'a C,
+) -> impl Iterator<Item = (u32, u128)> + 'a {
+    let positions = (!crabs.is_empty()).then(|| {
+        let (min, max) = crate::cost::outermost(crabs);
+        min..=max
+    });
+    positions
+        .into_iter()
+        .flatten()
+        .map(move |target| (target, total_fuel(crabs, cost, target)))
+}
+
+/// Writes the [`fuel_curve`] of `crabs` as CSV, with `position` and `fuel`
+/// columns.
+pub fn write_fuel_csv<C: CostFunction + ?Sized, W: Write>(
+    crabs: &[Crab],
+    cost: &C,
+    out: &mut W,
+) -> std::io::Result<()> {
+    writeln!(out, "position,fuel")?;
+    for (position, fuel) in fuel_curve(crabs, cost) {
+        writeln!(out, "{},{}", position, fuel)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+use crate::{CappedSteps, Linear, PiecewiseLinear, Quadratic, Triangular};
+
+#[cfg(test)]
+fn align_brute_force<C: CostFunction + ?Sized>(crabs: &[Crab], cost: &C) -> u128 {
+    let (min, max) = crate::cost::outermost(crabs);
+    (min..=max)
+        .map(|target| total_fuel(crabs, cost, target))
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn test_align_given_example() {
+    // GIVEN
+    let crabs: Vec<Crab> = [16, 1, 2, 0, 4, 2, 7, 1, 2, 14]
+        .iter()
+        .map(|&position| Crab::at(position))
+        .collect();
+
+    // WHEN
+    let linear = align(&crabs, &Linear);
+    let triangular = align(&crabs, &Triangular);
+
+    // THEN
+    assert_eq!(
+        linear,
+        Some(Alignment {
+            positions: 2..=2,
+            fuel: 37
+        })
+    );
+    assert_eq!(
+        triangular,
+        Some(Alignment {
+            positions: 5..=5,
+            fuel: 168
+        })
+    );
+    assert_eq!(align(&[], &Linear), None);
+}
+
+#[test]
+fn test_align_reports_ties() {
+    // GIVEN
+    let crabs = [Crab::at(0), Crab::at(10), Crab::at(3), Crab::at(8)];
+
+    // WHEN
+    let linear = align(&crabs, &Linear);
+    let triangular = align(&crabs[..2], &Triangular);
+
+    // THEN
+    assert_eq!(
+        linear,
+        Some(Alignment {
+            positions: 3..=8,
+            fuel: 15
+        })
+    );
+    assert_eq!(
+        triangular,
+        Some(Alignment {
+            positions: 5..=5,
+            fuel: 30
+        })
+    );
+    assert_eq!(
+        align(&[Crab::at(0), Crab::at(1)], &Triangular),
+        Some(Alignment {
+            positions: 0..=1,
+            fuel: 1
+        })
+    );
+}
+
+#[test]
+fn test_write_fuel_csv() {
+    // GIVEN
+    let crabs = [Crab::at(3), Crab::at(0), Crab::at(1)];
+
+    // WHEN
+    let mut out = vec![];
+    write_fuel_csv(&crabs, &Triangular, &mut out).unwrap();
+    let mut empty = vec![];
+    write_fuel_csv(&[], &Triangular, &mut empty).unwrap();
+
+    // THEN
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "position,fuel\n0,7\n1,4\n2,5\n3,9\n"
+    );
+    assert_eq!(String::from_utf8(empty).unwrap(), "position,fuel\n");
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn prop_align_matches_brute_force(
+        crabs in proptest::collection::vec((0u32..300, 0u64..4), 1..30),
+        max_step in 1u32..20,
+    ) {
+        let crabs: Vec<Crab> = crabs
+            .into_iter()
+            .map(|(position, weight)| Crab { position, weight })
+            .collect();
+        let costs: Vec<Box<dyn CostFunction>> = vec![
+            Box::new(Linear),
+            Box::new(Triangular),
+            Box::new(Quadratic),
+            Box::new(CappedSteps { max_step }),
+            Box::new(PiecewiseLinear::new(vec![(1, 1), (max_step + 1, 3), (2 * max_step + 1, 4)])),
+        ];
+        for cost in costs.iter() {
+            let alignment = align(&crabs, cost.as_ref()).unwrap();
+            let cheapest: Vec<u32> = fuel_curve(&crabs, cost.as_ref())
+                .filter(|&(_, fuel)| fuel == alignment.fuel)
+                .map(|(position, _)| position)
+                .collect();
+            proptest::prop_assert_eq!(alignment.fuel, align_brute_force(&crabs, cost.as_ref()));
+            proptest::prop_assert_eq!(cheapest, alignment.positions.collect::<Vec<u32>>());
+        }
+    }
+}
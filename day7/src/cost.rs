@@ -0,0 +1,171 @@
+use crate::Crab;
+
+/// Fuel a crab burns to move some distance. Solvers rely on it being convex:
+/// every step costs at least as much as the previous one.
+pub trait CostFunction {
+    fn fuel(&self, distance: u32) -> u128;
+
+    /// Range of positions known to hold a cheapest alignment of `crabs`,
+    /// which must not be empty; by default, between the outermost crabs.
+    fn search_window(&self, crabs: &[Crab]) -> (u32, u32) {
+        outermost(crabs)
+    }
+}
+
+pub(crate) fn outermost(crabs: &[Crab]) -> (u32, u32) {
+    let positions = crabs.iter().map(|crab| crab.position);
+    (positions.clone().min().unwrap(), positions.max().unwrap())
+}
+
+/// One unit of fuel per step.
+pub struct Linear;
+
+impl CostFunction for Linear {
+    fn fuel(&self, distance: u32) -> u128 {
+        distance as u128
+    }
+
+    fn search_window(&self, crabs: &[Crab]) -> (u32, u32) {
+        match weighted_median(crabs) {
+            Some(median) => (median, median),
+            None => outermost(crabs),
+        }
+    }
+}
+
+/// Lowest position with at least half of the weight of `crabs` on or before
+/// it, which is where they line up for the least [`Linear`] fuel. `None`
+/// without crabs, or when they weigh nothing.
+pub fn weighted_median(crabs: &[Crab]) -> Option<u32> {
+    let weight: u128 = crabs.iter().map(|crab| crab.weight as u128).sum();
+    let mut sorted = crabs.to_vec();
+    sorted.sort_by_key(|crab| crab.position);
+    let mut before = 0;
+    sorted
+        .iter()
+        .find(|crab| {
+            before += crab.weight as u128;
+            weight > 0 && 2 * before >= weight
+        })
+        .map(|crab| crab.position)
+}
+
+/// Each step costs one more than the previous one: 1 + 2 + ... + distance.
+pub struct Triangular;
+
+impl CostFunction for Triangular {
+    fn fuel(&self, distance: u32) -> u128 {
+        let distance = distance as u128;
+        distance * (distance + 1) / 2
+    }
+
+    fn search_window(&self, crabs: &[Crab]) -> (u32, u32) {
+        // The total fuel extends to the convex function of a real x
+        //   f(x) = sum(Wi * ((x - Ki)^2 + abs(Ki - x))) / 2
+        //   f'(x) = W * (x - mean) + sum(Wi * sign(x - Ki)) / 2
+        // with W the total weight. As the last sum is between -W/2 and W/2, f
+        // is minimal somewhere within 1/2 of the (weighted) mean. Being
+        // convex, its minimum over integers is next to that point: between
+        // floor(mean - 1/2) and ceil(mean + 1/2), computed here exactly as
+        // (2 * sum -+ W) / 2W.
+        let weight: u128 = crabs.iter().map(|crab| crab.weight as u128).sum();
+        if weight == 0 {
+            return outermost(crabs);
+        }
+        let sum: u128 = crabs
+            .iter()
+            .map(|crab| crab.weight as u128 * crab.position as u128)
+            .sum();
+        let (min, max) = outermost(crabs);
+        let x_min_left = ((2 * sum).saturating_sub(weight) / (2 * weight)) as u32;
+        let x_min_right = (2 * sum + weight).div_ceil(2 * weight).min(max as u128) as u32;
+        (x_min_left.max(min), x_min_right)
+    }
+}
+
+/// The fuel grows with the square of the distance.
+pub struct Quadratic;
+
+impl CostFunction for Quadratic {
+    fn fuel(&self, distance: u32) -> u128 {
+        distance as u128 * distance as u128
+    }
+}
+
+/// Like [`Triangular`], but no step costs more than `max_step`.
+pub struct CappedSteps {
+    pub max_step: u32,
+}
+
+impl CostFunction for CappedSteps {
+    fn fuel(&self, distance: u32) -> u128 {
+        let growing = distance.min(self.max_step);
+        Triangular.fuel(growing) + (distance - growing) as u128 * self.max_step as u128
+    }
+}
+
+/// Steps cost a fixed amount of fuel within each band of distances, more in
+/// the further bands.
+pub struct PiecewiseLinear {
+    // (first step of the band, fuel per step) by increasing distance
+    bands: Vec<(u32, u128)>,
+}
+
+impl PiecewiseLinear {
+    /// Bands are given as their first step, from 1, and the fuel every step
+    /// costs until the next band. Steps before the first band are free.
+    ///
+    /// Panics unless bands start at increasing steps with non decreasing
+    /// costs, as the cost would not be convex otherwise.
+    pub fn new(bands: Vec<(u32, u128)>) -> PiecewiseLinear {
+        assert!(
+            bands.first().is_none_or(|&(first_step, _)| first_step >= 1)
+                && bands
+                    .windows(2)
+                    .all(|w| w[0].0 < w[1].0 && w[0].1 <= w[1].1),
+            "bands must start further and cost more one after the other"
+        );
+        PiecewiseLinear { bands }
+    }
+}
+
+impl CostFunction for PiecewiseLinear {
+    fn fuel(&self, distance: u32) -> u128 {
+        let ends = self
+            .bands
+            .iter()
+            .skip(1)
+            .map(|&(first_step, _)| first_step as u64)
+            .chain([u64::MAX]);
+        self.bands
+            .iter()
+            .zip(ends)
+            .map(|(&(first_step, cost), end)| {
+                // steps from first_step to end, excluded, are in this band
+                let last_step = (distance as u64).min(end - 1);
+                last_step.saturating_sub(first_step as u64 - 1) as u128 * cost
+            })
+            .sum()
+    }
+}
+
+#[test]
+fn test_fuel() {
+    assert_eq!(Linear.fuel(11), 11);
+    assert_eq!(Triangular.fuel(11), 66);
+    assert_eq!(Quadratic.fuel(11), 121);
+    assert_eq!(CappedSteps { max_step: 3 }.fuel(2), 3);
+    assert_eq!(CappedSteps { max_step: 3 }.fuel(5), 1 + 2 + 3 + 3 + 3);
+    let bands = PiecewiseLinear::new(vec![(1, 1), (4, 2), (6, 10)]);
+    assert_eq!(bands.fuel(0), 0);
+    assert_eq!(bands.fuel(3), 3);
+    assert_eq!(bands.fuel(5), 3 + 2 * 2);
+    assert_eq!(bands.fuel(7), 3 + 2 * 2 + 2 * 10);
+    assert_eq!(PiecewiseLinear::new(vec![(3, 5)]).fuel(4), 10);
+}
+
+#[test]
+#[should_panic]
+fn test_piecewise_linear_must_be_convex() {
+    PiecewiseLinear::new(vec![(1, 2), (4, 1)]);
+}
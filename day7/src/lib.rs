@@ -0,0 +1,156 @@
+use aoc_common::{parse_token, ParseError, Solution};
+
+mod align;
+mod cost;
+mod space;
+
+pub use align::{align, fuel_curve, total_fuel, write_fuel_csv, Alignment, Crab};
+pub use cost::{
+    weighted_median, CappedSteps, CostFunction, Linear, PiecewiseLinear, Quadratic, Triangular,
+};
+pub use space::{
+    align_euclidean, align_manhattan, euclidean_fuel, manhattan_fuel, EuclideanAlignment,
+    ManhattanAlignment, SpatialCrab,
+};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<u32>;
+    type Answer1 = u128;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(positions: &Vec<u32>) -> u128 {
+        compute_part1(positions)
+    }
+
+    fn part2(positions: &Vec<u32>) -> u128 {
+        compute_part2(positions)
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    input
+        .split(",")
+        .map(|f| parse_token(1, input, f, "a crab position"))
+        .collect()
+}
+
+#[test]
+fn test_parse_reports_malformed_position() {
+    // GIVEN
+    let input = "16,1,2,0,4,2,7,,2,14";
+
+    // WHEN
+    let r = parse_input(input);
+
+    // THEN
+    let err = r.unwrap_err();
+    assert_eq!((err.line, err.column), (1, 16));
+    assert_eq!(err.token, "");
+}
+
+#[test]
+fn test_parse_given_example_input() {
+    // GIVEN
+    let input = "16,1,2,0,4,2,7,1,2,14";
+
+    // WHEN
+    let positions = parse_input(input).unwrap();
+
+    // THEN
+    assert!(positions == vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14]);
+}
+
+fn crabs(positions: &[u32]) -> Vec<Crab> {
+    positions
+        .iter()
+        .map(|&position| Crab::at(position))
+        .collect()
+}
+
+fn compute_part1(positions: &[u32]) -> u128 {
+    align(&crabs(positions), &Linear).unwrap().fuel
+}
+
+fn compute_part2(positions: &[u32]) -> u128 {
+    align(&crabs(positions), &Triangular).unwrap().fuel
+}
+
+#[cfg(test)]
+fn compute_part2_brute_force(positions: &[u32]) -> u128 {
+    let crabs = crabs(positions);
+    let (min, max) = cost::outermost(&crabs);
+    (min..=max)
+        .map(|x| total_fuel(&crabs, &Triangular, x))
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn test_part_2_far_away_crabs() {
+    // GIVEN
+    let positions = vec![u32::MAX, u32::MAX - 1, u32::MAX - 7, 4_000_000_000];
+
+    // WHEN
+    let r = compute_part2(&positions);
+
+    // THEN
+    assert_eq!(r, 32627139051147281);
+    assert_eq!(
+        compute_part2(&[0, u32::MAX]),
+        2 * Triangular.fuel(u32::MAX / 2 + 1) - (u32::MAX / 2 + 1) as u128
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn prop_part_2_matches_brute_force(
+        base in 0u32..u32::MAX - 1000,
+        offsets in proptest::collection::vec(0u32..1000, 1..40),
+    ) {
+        let positions: Vec<u32> = offsets.iter().map(|offset| base + offset).collect();
+        proptest::prop_assert_eq!(compute_part2(&positions), compute_part2_brute_force(&positions));
+    }
+}
+
+#[test]
+fn part_1_given_example() {
+    let input = "16,1,2,0,4,2,7,1,2,14";
+
+    // WHEN
+    let positions = parse_input(input).unwrap();
+    let r = compute_part1(&positions);
+
+    // THEN
+    assert!(r == 37);
+}
+
+#[test]
+fn part_2_given_example() {
+    let input = "16,1,2,0,4,2,7,1,2,14";
+
+    // WHEN
+    let positions = parse_input(input).unwrap();
+    let r = compute_part2(&positions);
+
+    // THEN
+    assert!(r == 168);
+}
+
+#[test]
+fn test_solve_tolerates_crlf_bom_and_trailing_newlines() {
+    // GIVEN
+    let input = "\u{feff}16,1,2,0,4,2,7,1,2,14\r\n\r\n";
+
+    // WHEN
+    let r = aoc_common::solve::<Day7>(input);
+
+    // THEN
+    assert_eq!(r, Ok((37, 168)));
+}
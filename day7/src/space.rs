@@ -0,0 +1,306 @@
+use crate::{align, Crab, Linear};
+
+/// A crab in `D` dimensions, standing for `weight` crabs there.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpatialCrab<const D: usize> {
+    pub position: [u32; D],
+    pub weight: u64,
+}
+
+/// Grid position where crabs line up for the least fuel, when they move one
+/// unit of fuel per step along any axis.
+#[derive(Debug, PartialEq)]
+pub struct ManhattanAlignment<const D: usize> {
+    pub position: [u32; D],
+    pub fuel: u128,
+}
+
+/// Point where crabs line up for the least fuel, when they burn fuel in
+/// proportion of the straight line distance they move.
+#[derive(Debug, PartialEq)]
+pub struct EuclideanAlignment<const D: usize> {
+    pub position: [f64; D],
+    pub fuel: f64,
+}
+
+/// Total fuel for all `crabs` to move to `target`, one unit per step along
+/// any axis.
+pub fn manhattan_fuel<const D: usize>(crabs: &[SpatialCrab<D>], target: [u32; D]) -> u128 {
+    crabs
+        .iter()
+        .map(|crab| {
+            let distance: u128 = (0..D)
+                .map(|axis| crab.position[axis].abs_diff(target[axis]) as u128)
+                .sum();
+            crab.weight as u128 * distance
+        })
+        .sum()
+}
+
+/// Total fuel for all `crabs` to move to `target`, in a straight line.
+pub fn euclidean_fuel<const D: usize>(crabs: &[SpatialCrab<D>], target: [f64; D]) -> f64 {
+    crabs
+        .iter()
+        .map(|crab| crab.weight as f64 * distance(&to_point(crab), &target))
+        .sum()
+}
+
+/// Cheapest grid position for `crabs` with Manhattan distances, or `None`
+/// without crabs.
+///
+/// The fuel is a sum over axes that do not depend on each other, so the
+/// alignment is the weighted median of each axis.
+pub fn align_manhattan<const D: usize>(crabs: &[SpatialCrab<D>]) -> Option<ManhattanAlignment<D>> {
+    if crabs.is_empty() {
+        return None;
+    }
+    let mut position = [0; D];
+    for (axis, coordinate) in position.iter_mut().enumerate() {
+        let projected: Vec<Crab> = crabs
+            .iter()
+            .map(|crab| Crab {
+                position: crab.position[axis],
+                weight: crab.weight,
+            })
+            .collect();
+        *coordinate = *align(&projected, &Linear)?.positions.start();
+    }
+    Some(ManhattanAlignment {
+        position,
+        fuel: manhattan_fuel(crabs, position),
+    })
+}
+
+const MAX_ITERATIONS: u32 = 100_000;
+
+/// Point for `crabs` to line up on with the least straight line fuel, i.e.
+/// their geometric median, or `None` without crabs. Iterates until the point
+/// moves by less than `tolerance`.
+///
+/// Uses Weiszfeld's iteration: from the weighted centroid, each step moves to
+/// the mean of the crabs weighted by `weight / distance`. When the point lands
+/// on a crab, that crab is left out of the mean and the step shortened as
+/// Vardi and Zhang do, so that the iteration neither divides by zero nor
+/// gets stuck on a crab which is not the median.
+pub fn align_euclidean<const D: usize>(
+    crabs: &[SpatialCrab<D>],
+    tolerance: f64,
+) -> Option<EuclideanAlignment<D>> {
+    if crabs.is_empty() {
+        return None;
+    }
+    let total_weight: f64 = crabs.iter().map(|crab| crab.weight as f64).sum();
+    if total_weight == 0.0 {
+        let position = to_point(&crabs[0]);
+        return Some(EuclideanAlignment {
+            position,
+            fuel: 0.0,
+        });
+    }
+    let mut point = [0.0; D];
+    for crab in crabs {
+        add_scaled(
+            &mut point,
+            &to_point(crab),
+            crab.weight as f64 / total_weight,
+        );
+    }
+    for _ in 0..MAX_ITERATIONS {
+        let next = weiszfeld_step(crabs, &point);
+        let moved = distance(&point, &next);
+        point = next;
+        if moved < tolerance {
+            break;
+        }
+    }
+    // Iterating towards a median on a crab only gets close to it: snap to
+    // the nearest crab when it is as cheap.
+    let nearest = crabs
+        .iter()
+        .map(to_point)
+        .min_by(|a, b| distance(a, &point).total_cmp(&distance(b, &point)))?;
+    let (fuel, nearest_fuel) = (euclidean_fuel(crabs, point), euclidean_fuel(crabs, nearest));
+    Some(if nearest_fuel <= fuel {
+        EuclideanAlignment {
+            position: nearest,
+            fuel: nearest_fuel,
+        }
+    } else {
+        EuclideanAlignment {
+            position: point,
+            fuel,
+        }
+    })
+}
+
+fn weiszfeld_step<const D: usize>(crabs: &[SpatialCrab<D>], point: &[f64; D]) -> [f64; D] {
+    let mut mean = [0.0; D];
+    let mut inverse_distances = 0.0;
+    // weight of the crabs right on the point, and the pull of the others
+    let mut weight_on_point = 0.0;
+    let mut pull = [0.0; D];
+    for crab in crabs.iter().filter(|crab| crab.weight > 0) {
+        let crab_point = to_point(crab);
+        let crab_distance = distance(&crab_point, point);
+        if crab_distance == 0.0 {
+            weight_on_point += crab.weight as f64;
+            continue;
+        }
+        let scale = crab.weight as f64 / crab_distance;
+        add_scaled(&mut mean, &crab_point, scale);
+        inverse_distances += scale;
+        add_scaled(&mut pull, &crab_point, scale);
+        add_scaled(&mut pull, point, -scale);
+    }
+    if inverse_distances == 0.0 {
+        // every crab is on the point
+        return *point;
+    }
+    mean.iter_mut().for_each(|x| *x /= inverse_distances);
+    if weight_on_point == 0.0 {
+        return mean;
+    }
+    let pull = norm(&pull);
+    if pull <= weight_on_point {
+        // the crabs on the point hold it against all others: it is optimal
+        return *point;
+    }
+    let kept = weight_on_point / pull;
+    let mut next = [0.0; D];
+    add_scaled(&mut next, &mean, 1.0 - kept);
+    add_scaled(&mut next, point, kept);
+    next
+}
+
+fn to_point<const D: usize>(crab: &SpatialCrab<D>) -> [f64; D] {
+    crab.position.map(|x| x as f64)
+}
+
+fn add_scaled<const D: usize>(sum: &mut [f64; D], point: &[f64; D], scale: f64) {
+    for (x, y) in sum.iter_mut().zip(point) {
+        *x += y * scale;
+    }
+}
+
+fn norm<const D: usize>(vector: &[f64; D]) -> f64 {
+    vector.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+fn distance<const D: usize>(a: &[f64; D], b: &[f64; D]) -> f64 {
+    let mut difference = *a;
+    add_scaled(&mut difference, b, -1.0);
+    norm(&difference)
+}
+
+#[cfg(test)]
+fn crab<const D: usize>(position: [u32; D], weight: u64) -> SpatialCrab<D> {
+    SpatialCrab { position, weight }
+}
+
+#[test]
+fn test_align_manhattan() {
+    // GIVEN
+    let crabs = [
+        crab([0, 0], 1),
+        crab([10, 2], 1),
+        crab([4, 9], 1),
+        crab([7, 3], 5),
+    ];
+
+    // WHEN
+    let r = align_manhattan(&crabs);
+
+    // THEN
+    assert_eq!(
+        r,
+        Some(ManhattanAlignment {
+            position: [7, 3],
+            fuel: 10 + 4 + 9
+        })
+    );
+    assert_eq!(align_manhattan::<3>(&[]), None);
+}
+
+#[test]
+fn test_align_euclidean_equilateral_triangle() {
+    // GIVEN the corners of a (nearly) equilateral triangle, centered on the
+    // centroid
+    let crabs = [crab([0, 0], 1), crab([1000, 0], 1), crab([500, 866], 1)];
+
+    // WHEN
+    let r = align_euclidean(&crabs, 1e-9).unwrap();
+
+    // THEN the median is where all sides are seen at 120 degrees
+    let expected = [500.0, 288.6751345948129];
+    assert!(distance(&r.position, &expected) < 1e-3, "{:?}", r.position);
+}
+
+#[test]
+fn test_align_euclidean_on_a_heavy_crab() {
+    // GIVEN a crab heavier than all others together
+    let crabs = [
+        crab([0, 0, 0], 1),
+        crab([5, 5, 5], 4),
+        crab([9, 0, 2], 1),
+        crab([3, 8, 1], 1),
+    ];
+
+    // WHEN
+    let r = align_euclidean(&crabs, 1e-12).unwrap();
+
+    // THEN it does not move, even if the centroid is elsewhere
+    assert_eq!(r.position, [5.0, 5.0, 5.0]);
+    assert_eq!(r.fuel, euclidean_fuel(&crabs, [5.0, 5.0, 5.0]));
+}
+
+#[test]
+fn test_align_euclidean_on_a_line() {
+    // GIVEN crabs on a line, as in the 1-D puzzle
+    let crabs: Vec<SpatialCrab<2>> = [16, 1, 2, 0, 4, 2, 7, 1, 2, 14]
+        .iter()
+        .map(|&x| crab([x, 3], 1))
+        .collect();
+
+    // WHEN
+    let r = align_euclidean(&crabs, 1e-9).unwrap();
+
+    // THEN
+    assert!((r.fuel - 37.0).abs() < 1e-6, "{}", r.fuel);
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn prop_align_manhattan_matches_brute_force(
+        crabs in proptest::collection::vec(([0u32..12, 0u32..12], 0u64..4), 1..10),
+    ) {
+        let crabs: Vec<SpatialCrab<2>> = crabs
+            .into_iter()
+            .map(|(position, weight)| crab(position, weight))
+            .collect();
+        let brute_force = (0..12)
+            .flat_map(|x| (0..12).map(move |y| [x, y]))
+            .map(|target| manhattan_fuel(&crabs, target))
+            .min()
+            .unwrap();
+        proptest::prop_assert_eq!(align_manhattan(&crabs).unwrap().fuel, brute_force);
+    }
+
+    #[test]
+    fn prop_align_euclidean_is_a_local_minimum(
+        crabs in proptest::collection::vec(([0u32..100, 0u32..100, 0u32..100], 1u64..4), 1..10),
+    ) {
+        let crabs: Vec<SpatialCrab<3>> = crabs
+            .into_iter()
+            .map(|(position, weight)| crab(position, weight))
+            .collect();
+        let r = align_euclidean(&crabs, 1e-10).unwrap();
+        for axis in 0..3 {
+            for step in [-1e-3, 1e-3] {
+                let mut moved = r.position;
+                moved[axis] += step;
+                proptest::prop_assert!(euclidean_fuel(&crabs, moved) >= r.fuel - 1e-6);
+            }
+        }
+    }
+}
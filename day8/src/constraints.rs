@@ -0,0 +1,535 @@
+use crate::wiring::{is_wire, wire_index};
+use crate::{DecodeError, Font, NoteEntry, Wiring};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+/// Why the segments a wire can be connected to were narrowed down.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reason {
+    /// The wire is on in `pattern`, which can only show one of `glyphs`.
+    Lit { pattern: String, glyphs: Vec<char> },
+    /// The wire is off in `pattern`, which can only show one of `glyphs`.
+    Off { pattern: String, glyphs: Vec<char> },
+    /// Another wire is known to be connected to `segment`.
+    Taken { wire: char, segment: char },
+    /// No other wire can be connected to `segment`.
+    OnlyWire { segment: char },
+}
+
+/// One deduction made while solving a wiring, in order.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+    /// `pattern` can only show one of `glyphs`.
+    Glyphs { pattern: String, glyphs: Vec<char> },
+    /// `wire` can only be connected to one of `segments`.
+    Segments {
+        wire: char,
+        segments: String,
+        reason: Reason,
+    },
+    /// Nothing left to deduce: try connecting `wire` to `segment`.
+    Guess { wire: char, segment: char },
+    /// The deductions so far cannot all hold.
+    Contradiction,
+}
+
+fn fmt_glyphs(glyphs: &[char]) -> String {
+    match glyphs {
+        [] => "no glyph".to_string(),
+        [glyph] => glyph.to_string(),
+        [first @ .., last] => {
+            let first: Vec<String> = first.iter().map(|glyph| glyph.to_string()).collect();
+            format!("{} or {}", first.join(", "), last)
+        }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reason::Lit { pattern, glyphs } => {
+                write!(f, "on in {}, which shows {}", pattern, fmt_glyphs(glyphs))
+            }
+            Reason::Off { pattern, glyphs } => {
+                write!(f, "off in {}, which shows {}", pattern, fmt_glyphs(glyphs))
+            }
+            Reason::Taken { wire, segment } => {
+                write!(f, "wire {} is segment {}", wire, segment)
+            }
+            Reason::OnlyWire { segment } => {
+                write!(f, "no other wire can be segment {}", segment)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Step::Glyphs { pattern, glyphs } => {
+                write!(f, "{} shows {}", pattern, fmt_glyphs(glyphs))
+            }
+            Step::Segments {
+                wire,
+                segments,
+                reason,
+            } => write!(f, "wire {} is one of {}: {}", wire, segments, reason),
+            Step::Guess { wire, segment } => {
+                write!(f, "guess wire {} is segment {}", wire, segment)
+            }
+            Step::Contradiction => write!(f, "contradiction"),
+        }
+    }
+}
+
+/// A wiring solved by constraint propagation, or why there is none, with the
+/// deductions that led there.
+#[derive(Debug)]
+pub struct Explanation {
+    pub wiring: Result<Wiring, DecodeError>,
+    pub steps: Vec<Step>,
+}
+
+// A scrambled pattern, as a bit per wire, and the glyphs it can still show,
+// as a bit per glyph of the font. Signal patterns all show different glyphs,
+// unlike output patterns.
+#[derive(Clone)]
+struct PatternGlyphs {
+    name: String,
+    wires: u32,
+    glyphs: u64,
+    distinct: bool,
+}
+
+// What is still possible: for each wire, a bit per segment it can be
+// connected to, and for each pattern, the glyphs it can show.
+#[derive(Clone)]
+pub(crate) struct Network {
+    names: Vec<char>,
+    shapes: Vec<u32>,
+    all_segments: u32,
+    wires: Vec<u32>,
+    patterns: Vec<PatternGlyphs>,
+}
+
+fn segment_name(segment: usize) -> char {
+    (b'a' + segment as u8) as char
+}
+
+fn bits(bits: u64) -> impl Iterator<Item = usize> {
+    (0..64).filter(move |bit| bits & 1 << bit != 0)
+}
+
+fn segment_names(segments: u32) -> String {
+    bits(segments as u64).map(segment_name).collect()
+}
+
+impl Network {
+    pub(crate) fn new(
+        font: &Font,
+        signal_patterns: &[HashSet<char>],
+        output_patterns: &[HashSet<char>],
+    ) -> Network {
+        let shapes: Vec<u32> = font.shapes().collect();
+        let signal_patterns = signal_patterns.iter().map(|pattern| (pattern, true));
+        let output_patterns = output_patterns.iter().map(|pattern| (pattern, false));
+        let patterns = signal_patterns
+            .chain(output_patterns)
+            .map(|(pattern, distinct)| {
+                let mut name: Vec<char> = pattern.iter().copied().collect();
+                name.sort();
+                let known = pattern.iter().all(|&wire| is_wire(wire, font.segments()));
+                // a pattern with wires the display does not have shows nothing
+                let glyphs = (0..shapes.len())
+                    .filter(|&glyph| known && shapes[glyph].count_ones() as usize == pattern.len())
+                    .fold(0, |glyphs, glyph| glyphs | 1 << glyph);
+                PatternGlyphs {
+                    name: name.into_iter().collect(),
+                    wires: pattern
+                        .iter()
+                        .filter(|_| known)
+                        .fold(0, |wires, &wire| wires | 1 << wire_index(wire)),
+                    glyphs,
+                    distinct,
+                }
+            })
+            .collect();
+        let all_segments = ((1u64 << font.segments()) - 1) as u32;
+        Network {
+            names: font.glyphs().collect(),
+            shapes,
+            all_segments,
+            wires: vec![all_segments; font.segments()],
+            patterns,
+        }
+    }
+
+    fn glyph_names(&self, glyphs: u64) -> Vec<char> {
+        bits(glyphs).map(|glyph| self.names[glyph]).collect()
+    }
+
+    fn restrict(&mut self, wire: usize, segments: u32, reason: Reason, steps: &mut Vec<Step>) {
+        let restricted = self.wires[wire] & segments;
+        if restricted != self.wires[wire] {
+            self.wires[wire] = restricted;
+            steps.push(Step::Segments {
+                wire: segment_name(wire),
+                segments: segment_names(restricted),
+                reason,
+            });
+        }
+    }
+
+    fn restrict_glyphs(&mut self, pattern: usize, glyphs: u64, steps: &mut Vec<Step>) {
+        let restricted = self.patterns[pattern].glyphs & glyphs;
+        if restricted != self.patterns[pattern].glyphs {
+            self.patterns[pattern].glyphs = restricted;
+            steps.push(Step::Glyphs {
+                pattern: self.patterns[pattern].name.clone(),
+                glyphs: self.glyph_names(restricted),
+            });
+        }
+    }
+
+    // whether `pattern` can show `glyph` as far as single wires can tell
+    fn can_show(&self, pattern: &PatternGlyphs, glyph: usize) -> bool {
+        let segments = self.shapes[glyph];
+        let mut covered = 0;
+        for wire in 0..self.wires.len() {
+            if pattern.wires & 1 << wire != 0 {
+                if self.wires[wire] & segments == 0 {
+                    return false;
+                }
+                covered |= self.wires[wire] & segments;
+            } else if self.wires[wire] & !segments == 0 {
+                return false;
+            }
+        }
+        covered == segments
+    }
+
+    // one round of every rule, returning whether anything changed
+    fn propagate_once(&mut self, steps: &mut Vec<Step>) -> bool {
+        let before = (self.wires.clone(), self.glyphs());
+        for index in 0..self.patterns.len() {
+            let pattern = &self.patterns[index];
+            let glyphs = bits(pattern.glyphs)
+                .filter(|&glyph| self.can_show(pattern, glyph))
+                .fold(0, |glyphs, glyph| glyphs | 1 << glyph);
+            self.restrict_glyphs(index, glyphs, steps);
+
+            let pattern = self.patterns[index].clone();
+            let (mut on, mut off) = (0, 0);
+            for glyph in bits(pattern.glyphs) {
+                on |= self.shapes[glyph];
+                off |= !self.shapes[glyph] & self.all_segments;
+            }
+            let glyphs = self.glyph_names(pattern.glyphs);
+            for wire in 0..self.wires.len() {
+                let (segments, reason) = if pattern.wires & 1 << wire != 0 {
+                    let (pattern, glyphs) = (pattern.name.clone(), glyphs.clone());
+                    (on, Reason::Lit { pattern, glyphs })
+                } else {
+                    let (pattern, glyphs) = (pattern.name.clone(), glyphs.clone());
+                    (off, Reason::Off { pattern, glyphs })
+                };
+                self.restrict(wire, segments, reason, steps);
+            }
+        }
+        for wire in 0..self.wires.len() {
+            if self.wires[wire].count_ones() == 1 {
+                let segment = self.wires[wire];
+                for other in (0..self.wires.len()).filter(|&other| other != wire) {
+                    let reason = Reason::Taken {
+                        wire: segment_name(wire),
+                        segment: segment_name(segment.trailing_zeros() as usize),
+                    };
+                    self.restrict(other, !segment, reason, steps);
+                }
+            }
+        }
+        for segment in 0..self.wires.len() {
+            let wires: Vec<usize> = (0..self.wires.len())
+                .filter(|&wire| self.wires[wire] & 1 << segment != 0)
+                .collect();
+            if let [wire] = wires[..] {
+                let reason = Reason::OnlyWire {
+                    segment: segment_name(segment),
+                };
+                self.restrict(wire, 1 << segment, reason, steps);
+            }
+        }
+        for index in 0..self.patterns.len() {
+            let glyphs = self.patterns[index].glyphs;
+            if self.patterns[index].distinct && glyphs.count_ones() == 1 {
+                let others: Vec<usize> = (0..self.patterns.len())
+                    .filter(|&other| other != index && self.patterns[other].distinct)
+                    .collect();
+                for other in others {
+                    self.restrict_glyphs(other, !glyphs, steps);
+                }
+            }
+        }
+        before != (self.wires.clone(), self.glyphs())
+    }
+
+    fn glyphs(&self) -> Vec<u64> {
+        self.patterns.iter().map(|pattern| pattern.glyphs).collect()
+    }
+
+    fn contradicts(&self) -> bool {
+        self.wires.contains(&0) || self.patterns.iter().any(|pattern| pattern.glyphs == 0)
+    }
+
+    // every wiring left that `fits`, after propagating as far as possible and
+    // guessing when stuck
+    pub(crate) fn solve(
+        mut self,
+        fits: &dyn Fn(&Wiring) -> bool,
+        steps: &mut Vec<Step>,
+    ) -> Vec<Wiring> {
+        while !self.contradicts() && self.propagate_once(steps) {}
+        if self.contradicts() {
+            steps.push(Step::Contradiction);
+            return vec![];
+        }
+        let undecided = (0..self.wires.len())
+            .filter(|&wire| self.wires[wire].count_ones() > 1)
+            .min_by_key(|&wire| self.wires[wire].count_ones());
+        let Some(wire) = undecided else {
+            let segments = self
+                .wires
+                .iter()
+                .map(|segments| segments.trailing_zeros() as u8)
+                .collect();
+            let wiring = Wiring::from_segments(segments);
+            if fits(&wiring) {
+                return vec![wiring];
+            }
+            steps.push(Step::Contradiction);
+            return vec![];
+        };
+        let mut wirings = vec![];
+        for segment in bits(self.wires[wire] as u64) {
+            steps.push(Step::Guess {
+                wire: segment_name(wire),
+                segment: segment_name(segment),
+            });
+            let mut guess = self.clone();
+            guess.wires[wire] = 1 << segment;
+            wirings.extend(guess.solve(fits, steps));
+        }
+        wirings
+    }
+
+    // the single wiring left that `fits`, with the deductions made
+    pub(crate) fn explain(self, fits: &dyn Fn(&Wiring) -> bool) -> Explanation {
+        let mut steps = vec![];
+        let mut wirings = self.solve(fits, &mut steps);
+        let wiring = match wirings.len() {
+            0 => Err(DecodeError::NoWiring),
+            1 => Ok(wirings.remove(0)),
+            _ => Err(DecodeError::AmbiguousWiring {
+                wirings: wirings.len(),
+            }),
+        };
+        Explanation { wiring, steps }
+    }
+}
+
+/// Wiring turning the signal `patterns` into exactly the ten digits, found by
+/// narrowing down the segments each wire can be connected to, with every
+/// deduction made on the way.
+///
+/// Each pattern can only show the digits with as many segments, its wires are
+/// then connected to segments of these digits and the other wires to the other
+/// segments. No two wires are connected to the same segment, and no two
+/// patterns show the same digit. When these rules cannot narrow down anything
+/// more, the solver guesses the segment of a wire and goes on.
+pub fn explain_wiring(patterns: &[HashSet<char>]) -> Explanation {
+    let digits = Font::seven_segment_digits();
+    let fits = |wiring: &Wiring| wiring.shows_glyphs(&digits, patterns);
+    Network::new(&digits, patterns, &[]).explain(&fits)
+}
+
+/// What can be told of a note entry whose signal patterns may not all be
+/// there: the wirings that fit its patterns, output patterns included, and
+/// the numbers the output value can show with them.
+#[derive(Debug, PartialEq)]
+pub struct PartialDecoding {
+    pub wirings: Vec<Wiring>,
+    pub numbers: Vec<u32>,
+}
+
+impl PartialDecoding {
+    /// The number shown by the output value, when there is a single one.
+    pub fn number(&self) -> Option<u32> {
+        match self.numbers[..] {
+            [number] => Some(number),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes `note` with whatever signal patterns it has, using its output
+/// patterns as more evidence: each of them must show a digit too, although
+/// not necessarily different ones.
+pub fn decode_partial(note: &NoteEntry) -> PartialDecoding {
+    let output_patterns: Vec<HashSet<char>> = note
+        .output_value
+        .iter()
+        .map(|pattern| pattern.chars().collect())
+        .collect();
+    let digits = Font::seven_segment_digits();
+    let fits = |wiring: &Wiring| wiring.fits(&digits, &note.signal_patterns, &output_patterns);
+    let network = Network::new(&digits, &note.signal_patterns, &output_patterns);
+    let wirings = network.solve(&fits, &mut vec![]);
+    let numbers: BTreeSet<u32> = wirings
+        .iter()
+        .filter_map(|wiring| wiring.read(note).ok())
+        .collect();
+    PartialDecoding {
+        wirings,
+        numbers: numbers.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+use crate::patterns;
+
+#[test]
+fn test_explain_wiring() {
+    // GIVEN
+    let patterns = patterns("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab");
+
+    // WHEN
+    let explanation = explain_wiring(&patterns);
+
+    // THEN
+    assert_eq!(explanation.wiring, crate::solve_wiring(&patterns));
+    assert_eq!(
+        explanation.steps[0].to_string(),
+        "wire a is one of bcef: off in bcdef, which shows 2, 3 or 5"
+    );
+    assert!(explanation
+        .steps
+        .iter()
+        .all(|step| !matches!(step, Step::Guess { .. } | Step::Contradiction)));
+    assert!(explanation.steps.contains(&Step::Glyphs {
+        pattern: "abcdef".to_string(),
+        glyphs: vec!['9']
+    }));
+}
+
+#[test]
+fn test_explain_wiring_reports_inconsistent_patterns() {
+    // GIVEN the example patterns, with a wire off in the 3
+    let patterns = patterns("acedgfb cdfbe gcdfa fbca dab cefabd cdfgeb eafb cagedb ab");
+
+    // WHEN
+    let explanation = explain_wiring(&patterns);
+
+    // THEN
+    assert_eq!(explanation.wiring, Err(DecodeError::NoWiring));
+    assert_eq!(explanation.steps.last(), Some(&Step::Contradiction));
+}
+
+#[test]
+fn test_explain_wiring_guesses_when_stuck() {
+    // GIVEN only the 8, which tells nothing: every wiring shows it
+    let patterns = patterns("abcdefg");
+
+    // WHEN
+    let explanation = explain_wiring(&patterns);
+
+    // THEN
+    assert_eq!(explanation.wiring, Err(DecodeError::NoWiring));
+    assert_eq!(
+        explanation.steps[0],
+        Step::Guess {
+            wire: 'a',
+            segment: 'a'
+        }
+    );
+}
+
+#[test]
+fn test_step_display() {
+    assert_eq!(
+        Step::Glyphs {
+            pattern: "ab".to_string(),
+            glyphs: vec!['1']
+        }
+        .to_string(),
+        "ab shows 1"
+    );
+    assert_eq!(
+        Step::Segments {
+            wire: 'c',
+            segments: "a".to_string(),
+            reason: Reason::Taken {
+                wire: 'd',
+                segment: 'b'
+            }
+        }
+        .to_string(),
+        "wire c is one of a: wire d is segment b"
+    );
+    assert_eq!(
+        Step::Segments {
+            wire: 'g',
+            segments: "e".to_string(),
+            reason: Reason::OnlyWire { segment: 'e' }
+        }
+        .to_string(),
+        "wire g is one of e: no other wire can be segment e"
+    );
+}
+
+#[cfg(test)]
+use crate::note;
+
+#[test]
+fn test_decode_partial_with_some_patterns() {
+    // GIVEN the example entry, without its 0, 2 and 8
+    let note = note("cdfbe fbcad dab cefabd cdfgeb eafb ab | cdfeb fcadb cdfeb cdbaf");
+
+    // WHEN
+    let r = decode_partial(&note);
+
+    // THEN
+    assert_eq!(r.wirings.len(), 1);
+    assert_eq!(r.number(), Some(5353));
+}
+
+#[test]
+fn test_decode_partial_with_outputs_only() {
+    // GIVEN
+    let one = note("| ab ab ab ab");
+    let seven_and_one = note("| ab bad ab bad");
+    let five_segments = note("| abcde abcde abcde abcde");
+
+    // WHEN
+    let r = [one, seven_and_one, five_segments].map(|note| decode_partial(&note));
+
+    // THEN the wires of the 1 can be swapped, and so can the other ones
+    assert_eq!(r[0].wirings.len(), 2 * 120);
+    assert_eq!(r[0].numbers, vec![1111]);
+    assert_eq!(r[1].wirings.len(), 2 * 24);
+    assert_eq!(r[1].numbers, vec![1717]);
+    assert_eq!(r[2].numbers, vec![2222, 3333, 5555]);
+    assert_eq!(r[2].number(), None);
+}
+
+#[test]
+fn test_decode_partial_reports_inconsistent_patterns() {
+    // GIVEN two signal patterns which can only be a 1
+    let note = note("ab cd | ab ab ab ab");
+
+    // WHEN
+    let r = decode_partial(&note);
+
+    // THEN
+    assert_eq!(r.wirings, vec![]);
+    assert_eq!(r.numbers, vec![]);
+}
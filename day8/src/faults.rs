@@ -0,0 +1,225 @@
+use crate::wiring::wirings;
+use crate::{Font, NoteEntry, Wiring};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+/// A broken segment of the display, named as the real segment from `a` to
+/// `g`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Fault {
+    /// The segment is lit whatever the digit.
+    StuckOn(char),
+    /// The segment never lights up.
+    StuckOff(char),
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Fault::StuckOn(segment) => write!(f, "segment {} stuck on", segment),
+            Fault::StuckOff(segment) => write!(f, "segment {} stuck off", segment),
+        }
+    }
+}
+
+/// A wiring and broken segments explaining every pattern of a note entry,
+/// with the numbers its output value can then show: broken segments can
+/// make digits look the same.
+#[derive(Debug, PartialEq)]
+pub struct Diagnosis {
+    pub wiring: Wiring,
+    pub faults: Vec<Fault>,
+    pub numbers: Vec<u32>,
+}
+
+/// Every explanation of a note entry with as few broken segments as
+/// possible. Empty when more segments than allowed would have to be broken.
+#[derive(Debug, PartialEq)]
+pub struct FaultReport {
+    pub explanations: Vec<Diagnosis>,
+}
+
+impl FaultReport {
+    /// Faults blamed by the explanations, with how many of them blame each,
+    /// most blamed first.
+    pub fn suspects(&self) -> Vec<(Fault, usize)> {
+        let mut blames: HashMap<Fault, usize> = HashMap::new();
+        for fault in self.explanations.iter().flat_map(|d| d.faults.iter()) {
+            *blames.entry(*fault).or_default() += 1;
+        }
+        let mut suspects: Vec<(Fault, usize)> = blames.into_iter().collect();
+        suspects.sort_by_key(|&(fault, blames)| (std::cmp::Reverse(blames), fault));
+        suspects
+    }
+}
+
+// Segments stuck on and stuck off, as bits, with up to `count` of them.
+fn fault_sets(count: usize) -> Vec<(u8, u8)> {
+    let mut sets: Vec<(u8, u8)> = vec![(0, 0)];
+    for segment in 0..7 {
+        let mut extended = vec![];
+        for &(on, off) in &sets {
+            if ((on | off).count_ones() as usize) < count {
+                extended.push((on | 1 << segment, off));
+                extended.push((on, off | 1 << segment));
+            }
+        }
+        sets.extend(extended);
+    }
+    sets.retain(|&(on, off)| (on | off).count_ones() as usize == count);
+    sets
+}
+
+fn faults(on: u8, off: u8) -> Vec<Fault> {
+    let name = |segment: u8| (b'a' + segment) as char;
+    let on = (0..7)
+        .filter(|segment| on & 1 << segment != 0)
+        .map(|segment| Fault::StuckOn(name(segment)));
+    let off = (0..7)
+        .filter(|segment| off & 1 << segment != 0)
+        .map(|segment| Fault::StuckOff(name(segment)));
+    on.chain(off).collect()
+}
+
+// Numbers the `outputs` can show, when each one can be any of the digits
+// looking like it. Notes have four outputs, so numbers fit.
+fn numbers(outputs: &[u32], shown: &[u32]) -> Vec<u32> {
+    let mut numbers = BTreeSet::from([0]);
+    for &output in outputs {
+        numbers = numbers
+            .iter()
+            .flat_map(|number| {
+                (0..10)
+                    .filter(|&digit| shown[digit] == output)
+                    .map(move |digit| number * 10 + digit as u32)
+            })
+            .collect();
+    }
+    numbers.into_iter().collect()
+}
+
+// Explanation of the note by `wiring` and the faults, if it is one: each
+// pattern must look like some digit, and signal patterns like different ones.
+fn diagnose_with(
+    wiring: &Wiring,
+    digits: &[u32],
+    (on, off): (u8, u8),
+    signals: &[u32],
+    outputs: &[u32],
+) -> Option<Diagnosis> {
+    let shown: Vec<u32> = digits
+        .iter()
+        .map(|&digit| (digit | on as u32) & !off as u32)
+        .collect();
+    let mut looks_alike: HashMap<u32, usize> = HashMap::new();
+    for &digit in &shown {
+        *looks_alike.entry(digit).or_default() += 1;
+    }
+    for &signal in signals {
+        let digits = looks_alike.get_mut(&signal)?;
+        *digits = digits.checked_sub(1)?;
+    }
+    if !outputs.iter().all(|output| shown.contains(output)) {
+        return None;
+    }
+    Some(Diagnosis {
+        wiring: wiring.clone(),
+        faults: faults(on, off),
+        numbers: numbers(outputs, &shown),
+    })
+}
+
+/// Explains `note` with a wiring and up to `max_faults` segments stuck on or
+/// stuck off, keeping the explanations with the fewest of them.
+///
+/// Tries every wiring against every set of faults, from none to
+/// `max_faults`, which gets slow beyond a few faults.
+pub fn diagnose(note: &NoteEntry, max_faults: usize) -> FaultReport {
+    let wirings = wirings();
+    let digits: Vec<u32> = Font::seven_segment_digits().shapes().collect();
+    for count in 0..=max_faults.min(7) {
+        let fault_sets = fault_sets(count);
+        let mut explanations = vec![];
+        for wiring in &wirings {
+            let signals: Vec<u32> = note
+                .signal_patterns
+                .iter()
+                .map(|pattern| wiring.unscramble(pattern.iter().copied()))
+                .collect();
+            let outputs: Vec<u32> = note
+                .output_value
+                .iter()
+                .map(|pattern| wiring.unscramble(pattern.chars()))
+                .collect();
+            explanations.extend(
+                fault_sets.iter().filter_map(|&faults| {
+                    diagnose_with(wiring, &digits, faults, &signals, &outputs)
+                }),
+            );
+        }
+        if !explanations.is_empty() {
+            return FaultReport { explanations };
+        }
+    }
+    FaultReport {
+        explanations: vec![],
+    }
+}
+
+#[cfg(test)]
+use crate::note;
+
+#[test]
+fn test_fault_sets() {
+    assert_eq!(fault_sets(0), vec![(0, 0)]);
+    assert_eq!(fault_sets(1).len(), 14);
+    assert_eq!(fault_sets(2).len(), 21 * 4);
+}
+
+#[test]
+fn test_diagnose_without_faults() {
+    // GIVEN
+    let note = note(
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+    );
+
+    // WHEN
+    let report = diagnose(&note, 2);
+
+    // THEN
+    assert_eq!(report.explanations.len(), 1);
+    assert_eq!(report.explanations[0].faults, vec![]);
+    assert_eq!(report.explanations[0].numbers, vec![5353]);
+    assert_eq!(report.suspects(), vec![]);
+}
+
+#[test]
+fn test_diagnose_stuck_off_segment() {
+    // GIVEN the example entry, with wire c of the bottom segment cut
+    let note = note("aedgfb dfbe gdfa fbad dab efabd dfgeb eafb agedb ab | dfeb fadb dfeb dbaf");
+
+    // WHEN
+    let report = diagnose(&note, 1);
+
+    // THEN
+    assert_eq!(report.suspects(), vec![(Fault::StuckOff('g'), 1)]);
+    assert_eq!(report.explanations[0].numbers, vec![5353]);
+    assert_eq!(diagnose(&note, 0).explanations, vec![]);
+}
+
+#[test]
+fn test_diagnose_stuck_on_segment() {
+    // GIVEN the example entry, with wire g of the bottom left segment always
+    // on: the 9 looks like the 8, and the 5 like the 6
+    let note = note(
+        "acedgfb cdfbeg gcdfa fbcadg dabg cefabdg cdfgeb eafbg cagedb abg | cdfebg fcadbg cdfebg cdbafg",
+    );
+
+    // WHEN
+    let report = diagnose(&note, 2);
+
+    // THEN
+    assert_eq!(report.suspects(), vec![(Fault::StuckOn('e'), 1)]);
+    assert_eq!(report.explanations[0].numbers, vec![5353, 5363, 6353, 6363]);
+    assert_eq!(Fault::StuckOn('e').to_string(), "segment e stuck on");
+}
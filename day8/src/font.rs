@@ -0,0 +1,424 @@
+use crate::constraints::{Explanation, Network};
+use crate::wiring::{is_wire, wire_index};
+use crate::{DecodeError, Wiring};
+use std::collections::{BTreeSet, HashSet};
+
+/// Segments of a seven-segment display, from `a` (top) to `g` (bottom):
+///
+/// ```text
+///  aaaa
+/// b    c
+/// b    c
+///  dddd
+/// e    f
+/// e    f
+///  gggg
+/// ```
+const SEVEN_SEGMENT_DIGITS: [(char, &str); 10] = [
+    ('0', "abcefg"),
+    ('1', "cf"),
+    ('2', "acdeg"),
+    ('3', "acdfg"),
+    ('4', "bcdf"),
+    ('5', "abdfg"),
+    ('6', "abdefg"),
+    ('7', "acf"),
+    ('8', "abcdefg"),
+    ('9', "abcdfg"),
+];
+
+const SEVEN_SEGMENT_HEX_LETTERS: [(char, &str); 6] = [
+    ('A', "abcdef"),
+    ('b', "bdefg"),
+    ('C', "abeg"),
+    ('d', "cdefg"),
+    ('E', "abdeg"),
+    ('F', "abde"),
+];
+
+/// Segments of a fourteen-segment display, the middle bar being split in `g`
+/// and `h`, with the vertical bars `j` and `m` and the diagonals `i`, `k`,
+/// `l` and `n` in between:
+///
+/// ```text
+///  aaaaaaa
+/// f i j k b
+/// f  ijk  b
+///  ggg hhh
+/// e  lmn  c
+/// e l m n c
+///  ddddddd
+/// ```
+const FOURTEEN_SEGMENT_ALPHANUMERIC: [(char, &str); 36] = [
+    ('0', "abcdefkl"),
+    ('1', "bc"),
+    ('2', "abdegh"),
+    ('3', "abcdh"),
+    ('4', "bcfgh"),
+    ('5', "acdfgh"),
+    ('6', "acdefgh"),
+    ('7', "abc"),
+    ('8', "abcdefgh"),
+    ('9', "abcdfgh"),
+    ('A', "abcefgh"),
+    ('B', "abcdhjm"),
+    ('C', "adef"),
+    ('D', "abcdjm"),
+    ('E', "adefg"),
+    ('F', "aefg"),
+    ('G', "acdefh"),
+    ('H', "bcefgh"),
+    ('I', "adjm"),
+    ('J', "bcde"),
+    ('K', "efgkn"),
+    ('L', "def"),
+    ('M', "bcefik"),
+    ('N', "bcefin"),
+    ('O', "abcdef"),
+    ('P', "abefgh"),
+    ('Q', "abcdefn"),
+    ('R', "abefghn"),
+    ('S', "acdhi"),
+    ('T', "ajm"),
+    ('U', "bcdef"),
+    ('V', "efkl"),
+    ('W', "bcefln"),
+    ('X', "ikln"),
+    ('Y', "ikm"),
+    ('Z', "adkl"),
+];
+
+/// Segments of a sixteen-segment display, laid out as the fourteen-segment
+/// one but with the top bar split in `a` and `b` and the bottom one in `e`
+/// and `f`, clockwise from the top left:
+///
+/// ```text
+///  aaa bbb
+/// h i j k c
+/// h  ijk  c
+///  ppp lll
+/// g  onm  d
+/// g o n m d
+///  fff eee
+/// ```
+///
+/// Only the square brackets light half a bar.
+const SIXTEEN_SEGMENT_ALPHANUMERIC: [(char, &str); 38] = [
+    ('0', "abcdefghko"),
+    ('1', "cd"),
+    ('2', "abcefglp"),
+    ('3', "abcdefl"),
+    ('4', "cdhlp"),
+    ('5', "abdefhlp"),
+    ('6', "abdefghlp"),
+    ('7', "abcd"),
+    ('8', "abcdefghlp"),
+    ('9', "abcdefhlp"),
+    ('A', "abcdghlp"),
+    ('B', "abcdefjln"),
+    ('C', "abefgh"),
+    ('D', "abcdefjn"),
+    ('E', "abefghp"),
+    ('F', "abghp"),
+    ('G', "abdefghl"),
+    ('H', "cdghlp"),
+    ('I', "abefjn"),
+    ('J', "cdefg"),
+    ('K', "ghkmp"),
+    ('L', "efgh"),
+    ('M', "cdghik"),
+    ('N', "cdghim"),
+    ('O', "abcdefgh"),
+    ('P', "abcghlp"),
+    ('Q', "abcdefghm"),
+    ('R', "abcghlmp"),
+    ('S', "abdefil"),
+    ('T', "abjn"),
+    ('U', "cdefgh"),
+    ('V', "ghko"),
+    ('W', "cdghmo"),
+    ('X', "ikmo"),
+    ('Y', "ikn"),
+    ('Z', "abefko"),
+    ('[', "afgh"),
+    (']', "bcde"),
+];
+
+/// The glyphs a segment display can show, each as the set of segments it
+/// lights up. Segments are named from `a`, and so are the wires connected
+/// to them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Font {
+    segments: usize,
+    // glyph and the segments it lights, as bits
+    glyphs: Vec<(char, u32)>,
+}
+
+impl Font {
+    /// A font for a display of `segments` segments, from its glyphs and the
+    /// segments they light up.
+    ///
+    /// Panics unless glyphs only use the `segments` first letters, and no two
+    /// of them have the same name or segments.
+    pub fn new(segments: usize, glyphs: &[(char, &str)]) -> Font {
+        assert!(segments <= 26 && glyphs.len() <= 64, "font too large");
+        let glyphs: Vec<(char, u32)> = glyphs
+            .iter()
+            .map(|&(glyph, lit)| {
+                let bits = lit.chars().fold(0, |bits, segment| {
+                    assert!(
+                        is_wire(segment, segments),
+                        "glyph {} uses unknown segment {}",
+                        glyph,
+                        segment
+                    );
+                    bits | 1 << wire_index(segment)
+                });
+                (glyph, bits)
+            })
+            .collect();
+        let names: HashSet<char> = glyphs.iter().map(|&(glyph, _)| glyph).collect();
+        let shapes: HashSet<u32> = glyphs.iter().map(|&(_, bits)| bits).collect();
+        assert!(
+            names.len() == glyphs.len() && shapes.len() == glyphs.len(),
+            "glyphs must have different names and segments"
+        );
+        Font { segments, glyphs }
+    }
+
+    /// The ten digits of a seven-segment display, as in the puzzle.
+    pub fn seven_segment_digits() -> Font {
+        Font::new(7, &SEVEN_SEGMENT_DIGITS)
+    }
+
+    /// The sixteen hexadecimal digits of a seven-segment display, with
+    /// `A b C d E F` as letters.
+    pub fn seven_segment_hex() -> Font {
+        let glyphs: Vec<(char, &str)> = SEVEN_SEGMENT_DIGITS
+            .into_iter()
+            .chain(SEVEN_SEGMENT_HEX_LETTERS)
+            .collect();
+        Font::new(7, &glyphs)
+    }
+
+    /// Digits and capital letters of a fourteen-segment display.
+    pub fn fourteen_segment_alphanumeric() -> Font {
+        Font::new(14, &FOURTEEN_SEGMENT_ALPHANUMERIC)
+    }
+
+    /// Digits, capital letters and square brackets of a sixteen-segment
+    /// display.
+    pub fn sixteen_segment_alphanumeric() -> Font {
+        Font::new(16, &SIXTEEN_SEGMENT_ALPHANUMERIC)
+    }
+
+    /// Number of segments of the display.
+    pub fn segments(&self) -> usize {
+        self.segments
+    }
+
+    /// Glyphs of the font, in order.
+    pub fn glyphs(&self) -> impl Iterator<Item = char> + '_ {
+        self.glyphs.iter().map(|&(glyph, _)| glyph)
+    }
+
+    // segments lit by each glyph, as bits, in order
+    pub(crate) fn shapes(&self) -> impl Iterator<Item = u32> + '_ {
+        self.glyphs.iter().map(|&(_, bits)| bits)
+    }
+
+    // glyph lighting exactly the segments of `bits`
+    pub(crate) fn glyph(&self, bits: u32) -> Option<char> {
+        self.glyphs
+            .iter()
+            .find(|&&(_, shape)| shape == bits)
+            .map(|&(glyph, _)| glyph)
+    }
+}
+
+fn wire_sets(patterns: &[&str]) -> Vec<HashSet<char>> {
+    patterns
+        .iter()
+        .map(|pattern| pattern.chars().collect())
+        .collect()
+}
+
+/// Wiring of a display of `font` whose signal patterns show different glyphs,
+/// all of them or not, and whose output patterns show glyphs too, with every
+/// deduction made on the way: see [`crate::explain_wiring`].
+pub fn explain_font_wiring(
+    font: &Font,
+    signal_patterns: &[&str],
+    output_patterns: &[&str],
+) -> Explanation {
+    let (signal_patterns, output_patterns) =
+        (wire_sets(signal_patterns), wire_sets(output_patterns));
+    let fits = |wiring: &Wiring| wiring.fits(font, &signal_patterns, &output_patterns);
+    Network::new(font, &signal_patterns, &output_patterns).explain(&fits)
+}
+
+/// Glyphs shown by the `output_patterns` of a display of `font`, decoded with
+/// the help of its `signal_patterns`. Several wirings are fine, as long as
+/// they all read the same.
+pub fn decode_with_font(
+    font: &Font,
+    signal_patterns: &[&str],
+    output_patterns: &[&str],
+) -> Result<String, DecodeError> {
+    let (signals, outputs) = (wire_sets(signal_patterns), wire_sets(output_patterns));
+    let fits = |wiring: &Wiring| wiring.fits(font, &signals, &outputs);
+    let wirings = Network::new(font, &signals, &outputs).solve(&fits, &mut vec![]);
+    let readings: BTreeSet<String> = wirings
+        .iter()
+        .map(|wiring| wiring.read_glyphs(font, output_patterns))
+        .collect::<Result<_, _>>()?;
+    match readings.len() {
+        0 => Err(DecodeError::NoWiring),
+        1 => Ok(readings.into_iter().next().unwrap()),
+        _ => Err(DecodeError::AmbiguousWiring {
+            wirings: wirings.len(),
+        }),
+    }
+}
+
+#[cfg(test)]
+fn scramble(font: &Font, wires: &str, text: &str) -> Vec<String> {
+    text.chars()
+        .map(|glyph| {
+            let (_, shape) = font.glyphs.iter().find(|&&(g, _)| g == glyph).unwrap();
+            wires
+                .chars()
+                .enumerate()
+                .filter(|&(segment, _)| shape & 1 << segment != 0)
+                .map(|(_, wire)| wire)
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_fonts() {
+    for (font, segments, glyphs) in [
+        (Font::seven_segment_digits(), 7, "0123456789"),
+        (Font::seven_segment_hex(), 7, "0123456789AbCdEF"),
+        (
+            Font::fourteen_segment_alphanumeric(),
+            14,
+            "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        ),
+        (
+            Font::sixteen_segment_alphanumeric(),
+            16,
+            "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ[]",
+        ),
+    ] {
+        assert_eq!(font.segments(), segments);
+        assert_eq!(font.glyphs().collect::<String>(), glyphs);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_font_glyphs_must_differ() {
+    Font::new(3, &[('a', "ab"), ('b', "ba")]);
+}
+
+#[test]
+#[should_panic]
+fn test_font_segments_must_exist() {
+    Font::new(3, &[('a', "abd")]);
+}
+
+#[test]
+fn test_decode_with_font_seven_segment_digits() {
+    // GIVEN
+    let signals = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
+    let signals: Vec<&str> = signals.split(' ').collect();
+
+    // WHEN
+    let r = decode_with_font(&Font::seven_segment_digits(), &signals, &["cdfeb", "fcadb"]);
+
+    // THEN
+    assert_eq!(r, Ok("53".to_string()));
+}
+
+#[test]
+fn test_decode_with_font_seven_segment_hex() {
+    // GIVEN
+    let font = Font::seven_segment_hex();
+    let glyphs: String = font.glyphs().collect();
+    let signals = scramble(&font, "gcbfdea", &glyphs);
+    let outputs = scramble(&font, "gcbfdea", "C0FFEE");
+
+    // WHEN
+    let signals: Vec<&str> = signals.iter().map(String::as_str).collect();
+    let outputs: Vec<&str> = outputs.iter().map(String::as_str).collect();
+    let r = decode_with_font(&font, &signals, &outputs);
+
+    // THEN
+    assert_eq!(r, Ok("C0FFEE".to_string()));
+}
+
+#[test]
+fn test_decode_with_font_fourteen_segment_alphanumeric() {
+    // GIVEN
+    let font = Font::fourteen_segment_alphanumeric();
+    let wires = "njcdahkbimglef";
+    let glyphs: String = font.glyphs().collect();
+    let signals = scramble(&font, wires, &glyphs);
+    let outputs = scramble(&font, wires, "HELL0W0RLD");
+
+    // WHEN
+    let signals: Vec<&str> = signals.iter().map(String::as_str).collect();
+    let outputs: Vec<&str> = outputs.iter().map(String::as_str).collect();
+    let explanation = explain_font_wiring(&font, &signals, &outputs);
+    let r = decode_with_font(&font, &signals, &outputs);
+
+    // THEN
+    let wiring = explanation.wiring.unwrap();
+    let segments: Option<String> = wires.chars().map(|wire| wiring.segment(wire)).collect();
+    assert_eq!(segments.as_deref(), Some("abcdefghijklmn"));
+    assert_eq!(r, Ok("HELL0W0RLD".to_string()));
+}
+
+#[test]
+fn test_decode_with_font_sixteen_segment_alphanumeric() {
+    // GIVEN
+    let font = Font::sixteen_segment_alphanumeric();
+    let wires = "pjcdahkbimglefon";
+    let glyphs: String = font.glyphs().collect();
+    let signals = scramble(&font, wires, &glyphs);
+    let outputs = scramble(&font, wires, "[HELL0]W0RLD");
+
+    // WHEN
+    let signals: Vec<&str> = signals.iter().map(String::as_str).collect();
+    let outputs: Vec<&str> = outputs.iter().map(String::as_str).collect();
+    let explanation = explain_font_wiring(&font, &signals, &outputs);
+    let r = decode_with_font(&font, &signals, &outputs);
+
+    // THEN
+    let wiring = explanation.wiring.unwrap();
+    let segments: Option<String> = wires.chars().map(|wire| wiring.segment(wire)).collect();
+    assert_eq!(segments.as_deref(), Some("abcdefghijklmnop"));
+    assert_eq!(r, Ok("[HELL0]W0RLD".to_string()));
+}
+
+#[test]
+fn test_decode_with_font_reports_unknown_glyph() {
+    // GIVEN
+    let font = Font::seven_segment_digits();
+    let signals = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
+    let signals: Vec<&str> = signals.split(' ').collect();
+
+    // WHEN
+    let r = decode_with_font(&font, &signals, &["cdfeb", "fca"]);
+
+    // THEN
+    assert_eq!(r, Err(DecodeError::NoWiring));
+    assert_eq!(
+        Wiring::from_segments(vec![2, 5, 6, 0, 1, 3, 4]).read_glyphs(&font, &["fca"]),
+        Err(DecodeError::UnknownGlyph {
+            pattern: "fca".to_string()
+        })
+    );
+}
@@ -0,0 +1,470 @@
+use aoc_common::{ParseError, Solution};
+use std::collections::HashSet;
+use std::fmt;
+
+mod constraints;
+mod faults;
+mod font;
+mod wiring;
+
+pub use constraints::{decode_partial, explain_wiring, Explanation, PartialDecoding, Reason, Step};
+pub use faults::{diagnose, Diagnosis, Fault, FaultReport};
+pub use font::{decode_with_font, explain_font_wiring, Font};
+pub use wiring::{decode_note, solve_wiring, DecodeError, Wiring};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<NoteEntry>;
+    type Answer1 = u32;
+    type Answer2 = OutputSum;
+
+    fn parse(input: &str) -> Result<Vec<NoteEntry>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(notes: &Vec<NoteEntry>) -> u32 {
+        compute_part1(notes)
+    }
+
+    fn part2(notes: &Vec<NoteEntry>) -> OutputSum {
+        compute_part2(notes)
+    }
+}
+
+#[derive(Debug)]
+pub struct NoteEntry {
+    signal_patterns: Vec<HashSet<char>>,
+    output_value: Vec<String>,
+}
+
+/// Parses a single note line; errors are reported as if it were line 1.
+fn parse_note_entry(input: &str) -> Result<NoteEntry, ParseError> {
+    let (signal_patterns, output_value) = input.trim().split_once("|").ok_or_else(|| {
+        ParseError::end_of_line(1, input, "'|' between signal patterns and output value")
+    })?;
+    let wires = |pattern: &str| -> Result<(), ParseError> {
+        match pattern.find(|c| !('a'..='g').contains(&c)) {
+            Some(_) => Err(ParseError::at(1, input, pattern, "wires 'a' to 'g'")),
+            None => Ok(()),
+        }
+    };
+    // the output value is a four-digit number
+    let output_value: Vec<&str> = output_value.split_ascii_whitespace().collect();
+    match output_value.get(4) {
+        Some(extra) => return Err(ParseError::at(1, input, extra, "four output patterns")),
+        None if output_value.len() < 4 => {
+            return Err(ParseError::end_of_line(1, input, "four output patterns"))
+        }
+        None => {}
+    }
+    Ok(NoteEntry {
+        signal_patterns: signal_patterns
+            .split_ascii_whitespace()
+            .map(|s| wires(s).map(|_| HashSet::from_iter(s.chars())))
+            .collect::<Result<_, _>>()?,
+        output_value: output_value
+            .into_iter()
+            .map(|f| wires(f).map(|_| f.to_string()))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+// wire sets of space separated patterns, for tests
+#[cfg(test)]
+fn patterns(patterns: &str) -> Vec<HashSet<char>> {
+    patterns
+        .split_ascii_whitespace()
+        .map(|pattern| pattern.chars().collect())
+        .collect()
+}
+
+// note entry of a well-formed line, for tests
+#[cfg(test)]
+fn note(input: &str) -> NoteEntry {
+    parse_note_entry(input).unwrap()
+}
+
+#[test]
+fn test_parse_note_entry() {
+    // GIVEN
+    let input =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+
+    // WHEN
+    let note = parse_note_entry(input).unwrap();
+
+    // THEN
+    assert_eq!(note.signal_patterns.len(), 10);
+    assert_eq!(
+        note.signal_patterns[0],
+        HashSet::from_iter("acedgfb".chars())
+    );
+    assert_eq!(note.signal_patterns[9], HashSet::from_iter("ab".chars()));
+    assert_eq!(note.output_value.len(), 4);
+    assert_eq!(note.output_value[0], "cdfeb");
+    assert_eq!(note.output_value[1], "fcadb");
+    assert_eq!(note.output_value[2], "cdfeb");
+    assert_eq!(note.output_value[3], "cdbaf");
+}
+
+fn parse_input(input: &str) -> Result<Vec<NoteEntry>, ParseError> {
+    input
+        .trim()
+        .split("\n")
+        .enumerate()
+        .map(|(index, line)| {
+            parse_note_entry(line).map_err(|err| ParseError {
+                line: index + 1,
+                ..err
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_reports_malformed_note() {
+    // GIVEN
+    let input =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+        edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec fcgedb cgb dgebacf gc
+        fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbx";
+
+    // WHEN
+    let missing_separator = parse_input(input);
+    let bad_wire = parse_note_entry(input.split("\n").nth(2).unwrap());
+
+    // THEN
+    let err = missing_separator.unwrap_err();
+    assert_eq!((err.line, err.column), (2, 89));
+    assert_eq!(err.token, "");
+    let err = bad_wire.unwrap_err();
+    assert_eq!((err.line, err.column), (1, 83));
+    assert_eq!(err.token, "cbx");
+}
+
+#[test]
+fn test_parse_reports_output_value_without_four_patterns() {
+    // GIVEN
+    let long = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf ab ab ab ab ab ab";
+    let short = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb";
+
+    // WHEN
+    let too_many = aoc_common::solve::<Day8>(long);
+    let too_few = parse_input(short);
+
+    // THEN
+    let err = too_many.unwrap_err();
+    assert_eq!((err.line, err.column), (1, 86));
+    assert_eq!(err.expected, "four output patterns");
+    let err = too_few.unwrap_err();
+    assert_eq!((err.line, err.column), (1, 73));
+    assert_eq!(err.token, "");
+}
+
+#[test]
+fn test_parse_given_example_input() {
+    // GIVEN
+    let input =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+        edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+        fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+        fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+        aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+        fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+        dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+        bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+        egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+        gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    // WHEN
+    let notes = parse_input(input).unwrap();
+
+    // THEN
+    assert_eq!(notes.len(), 10);
+}
+
+/**
+ * 0 -> 6 segments
+ * 1 -> 2 segments
+ * 2 -> 5 segments
+ * 3 -> 5 segments
+ * 4 -> 4 segments
+ * 5 -> 5 segments
+ * 6 -> 6 segments
+ * 7 -> 3 segments
+ * 8 -> 7 segments
+ * 9 -> 6 segments
+ *
+ * 2 segments: [1]
+ * 3 segments: [7]
+ * 4 segments: [4]
+ * 5 segments: [2, 3, 5]
+ * 6 segments: [0, 6, 9]
+ * 7 segments: [8]
+ */
+fn compute_part1(notes: &[NoteEntry]) -> u32 {
+    notes
+        .iter()
+        .map(|note| {
+            note.output_value
+                .iter()
+                .filter(|val| val.len() != 5 && val.len() != 6)
+                .count() as u32
+        })
+        .sum()
+}
+
+#[test]
+fn test_segment_inference() {
+    // GIVEN
+    let input =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+
+    // WHEN
+    let note = parse_note_entry(input).unwrap();
+    let wiring = explain_wiring(&note.signal_patterns).wiring.unwrap();
+
+    // THEN
+    assert_eq!(wiring.wire('a'), Some('d')); // top
+    assert_eq!(wiring.wire('g'), Some('c')); // bottom
+    assert_eq!(wiring.wire('d'), Some('f')); // middle
+    assert_eq!(wiring.wire('b'), Some('e')); // top left
+    assert_eq!(wiring.wire('e'), Some('g')); // bottom left
+    assert_eq!(wiring.wire('f'), Some('b')); // bottom right
+    assert_eq!(wiring.wire('c'), Some('a')); // top right
+}
+
+#[test]
+fn test_example_digits_decoding() {
+    // GIVEN
+    let input =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+
+    // WHEN
+    let note = parse_note_entry(input).unwrap();
+    let wiring = explain_wiring(&note.signal_patterns).wiring.unwrap();
+    let digits: Vec<Option<u8>> = note
+        .output_value
+        .iter()
+        .map(|pattern| wiring.digit(pattern))
+        .collect();
+
+    // THEN
+    assert_eq!(digits, vec![Some(5), Some(3), Some(5), Some(3)]);
+}
+
+// Output value of `note`, which may lack some of its signal patterns, as long
+// as every wiring still fitting it reads the same number.
+fn read_digits(note: &NoteEntry) -> Result<u32, DecodeError> {
+    let decoding = decode_partial(note);
+    match decoding.number() {
+        Some(number) => Ok(number),
+        None if decoding.numbers.is_empty() => Err(DecodeError::NoWiring),
+        None => Err(DecodeError::AmbiguousWiring {
+            wirings: decoding.wirings.len(),
+        }),
+    }
+}
+
+#[test]
+fn test_read_digits_on_first_example() {
+    let input =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+        edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+        fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+        fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+        aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+        fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+        dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+        bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+        egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+        gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+    let values = [8394, 9781, 1197, 9361, 4873, 8418, 4548, 1625, 8717, 4315];
+
+    // WHEN
+    let notes = parse_input(input).unwrap();
+
+    // THEN
+    for (note, expected_value) in notes.iter().zip(values) {
+        assert_eq!(read_digits(note), Ok(expected_value));
+    }
+}
+
+/// Sum of the output values of the note entries that could be decoded, with
+/// the line and the error of every one that could not.
+#[derive(Debug, PartialEq)]
+pub struct OutputSum {
+    pub sum: u32,
+    pub undecoded: Vec<(usize, DecodeError)>,
+}
+
+impl fmt::Display for OutputSum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.sum)?;
+        if !self.undecoded.is_empty() {
+            let skipped: Vec<String> = self
+                .undecoded
+                .iter()
+                .map(|(line, err)| format!("line {}: {}", line, err))
+                .collect();
+            write!(f, " (skipped {})", skipped.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn compute_part2(notes: &[NoteEntry]) -> OutputSum {
+    let mut output_sum = OutputSum {
+        sum: 0,
+        undecoded: vec![],
+    };
+    for (index, note) in notes.iter().enumerate() {
+        match read_digits(note) {
+            Ok(value) => output_sum.sum += value,
+            Err(err) => output_sum.undecoded.push((index + 1, err)),
+        }
+    }
+    output_sum
+}
+
+#[test]
+fn test_solve_skips_inconsistent_notes() {
+    // GIVEN the example entry, with a wire off in the 3 on line 2
+    let input =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf
+        acedgfb cdfbe gcdfa fbca dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+
+    // WHEN
+    let (_, r) = aoc_common::solve::<Day8>(input).unwrap();
+
+    // THEN
+    assert_eq!(
+        r,
+        OutputSum {
+            sum: 5353,
+            undecoded: vec![(2, DecodeError::NoWiring)]
+        }
+    );
+    assert_eq!(
+        r.to_string(),
+        "5353 (skipped line 2: no wiring matches the signal patterns)"
+    );
+}
+
+#[test]
+fn test_solve_decodes_notes_missing_signal_patterns() {
+    // GIVEN the example entry without the pattern of the 8, and with only
+    // the 0, 5 and 3 on line 2
+    let input = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb | cdfeb fcadb cdfeb cdbaf
+        cagedb cdfbe fbcad | cdfeb fcadb cdfeb cdbaf";
+
+    // WHEN
+    let (_, r) = aoc_common::solve::<Day8>(input).unwrap();
+
+    // THEN
+    assert_eq!(r.sum, 5353);
+    assert!(matches!(
+        r.undecoded[..],
+        [(2, DecodeError::AmbiguousWiring { .. })]
+    ));
+}
+
+#[test]
+fn test_decode_note_agrees_with_explain_wiring() {
+    // GIVEN
+    let input =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+        edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+        fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+        fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+        aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+        fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+        dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+        bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+        egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+        gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    // WHEN
+    let notes = parse_input(input).unwrap();
+
+    // THEN
+    for note in &notes {
+        let explained = explain_wiring(&note.signal_patterns).wiring;
+        assert_eq!(
+            decode_note(note),
+            explained.and_then(|wiring| wiring.read(note))
+        );
+    }
+}
+
+#[test]
+fn part_1_given_example() {
+    let input =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+        edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+        fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+        fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+        aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+        fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+        dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+        bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+        egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+        gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    // WHEN
+    let notes = parse_input(input).unwrap();
+    let r = compute_part1(&notes);
+
+    // THEN
+    assert!(r == 26);
+}
+
+#[test]
+fn part_2_given_example() {
+    let input =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+        edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+        fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+        fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+        aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+        fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+        dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+        bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+        egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+        gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    // WHEN
+    let notes = parse_input(input).unwrap();
+    let r = compute_part2(&notes);
+
+    // THEN
+    assert!(r.sum == 61229 && r.undecoded.is_empty());
+}
+
+#[test]
+fn test_solve_tolerates_crlf_bom_and_trailing_newlines() {
+    // GIVEN
+    let input =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+        edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+        fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+        fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+        aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+        fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+        dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+        bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+        egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+        gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
+";
+    let input = format!("\u{feff}{}\r\n", input.replace("\n", "\r\n"));
+
+    // WHEN
+    let r = aoc_common::solve::<Day8>(&input);
+
+    // THEN
+    assert_eq!(
+        r.map(|(r1, r2)| (r1, r2.to_string())),
+        Ok((26, "61229".to_string()))
+    );
+}
@@ -0,0 +1,298 @@
+use crate::{Font, NoteEntry};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Why the wires of a note entry could not be decoded.
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    /// No wiring turns the signal patterns into the glyphs of the font.
+    NoWiring,
+    /// Several wirings turn the signal patterns into the glyphs of the font.
+    AmbiguousWiring { wirings: usize },
+    /// An output pattern is not a glyph of the font with the wiring found.
+    UnknownGlyph { pattern: String },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::NoWiring => write!(f, "no wiring matches the signal patterns"),
+            DecodeError::AmbiguousWiring { wirings } => {
+                write!(f, "{} wirings match the signal patterns", wirings)
+            }
+            DecodeError::UnknownGlyph { pattern } => {
+                write!(f, "output pattern '{}' is not a glyph", pattern)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// The real segment each scrambled wire, from `a`, is connected to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wiring {
+    segments: Vec<u8>,
+}
+
+impl Wiring {
+    // wiring connecting wire `a` + n to segment `a` + `segments[n]`
+    pub(crate) fn from_segments(segments: Vec<u8>) -> Wiring {
+        Wiring { segments }
+    }
+
+    /// Real segment lit by `wire`, if the display has that wire.
+    pub fn segment(&self, wire: char) -> Option<char> {
+        is_wire(wire, self.segments.len()).then(|| (b'a' + self.segments[wire_index(wire)]) as char)
+    }
+
+    /// Wire connected to the real `segment`, if the display has that segment.
+    pub fn wire(&self, segment: char) -> Option<char> {
+        if !is_wire(segment, self.segments.len()) {
+            return None;
+        }
+        let wire = self
+            .segments
+            .iter()
+            .position(|&s| s as usize == wire_index(segment))?;
+        Some((b'a' + wire as u8) as char)
+    }
+
+    // real segments lit by `wires`, as bits
+    pub(crate) fn unscramble(&self, wires: impl IntoIterator<Item = char>) -> u32 {
+        wires
+            .into_iter()
+            .fold(0, |bits, wire| bits | 1 << self.segments[wire_index(wire)])
+    }
+
+    /// Glyph of `font` shown when the wires of `pattern` are on, if any.
+    pub fn glyph(&self, font: &Font, pattern: &str) -> Option<char> {
+        if !pattern
+            .chars()
+            .all(|wire| is_wire(wire, self.segments.len()))
+        {
+            return None;
+        }
+        font.glyph(self.unscramble(pattern.chars()))
+    }
+
+    /// Digit shown on a seven-segment display when the wires of `pattern` are
+    /// on, if any.
+    pub fn digit(&self, pattern: &str) -> Option<u8> {
+        self.glyph(&Font::seven_segment_digits(), pattern)
+            .map(|digit| digit.to_digit(10).unwrap() as u8)
+    }
+
+    // whether the signal `patterns` are exactly the glyphs of `font` with this
+    // wiring
+    pub(crate) fn shows_glyphs(&self, font: &Font, patterns: &[HashSet<char>]) -> bool {
+        let mut glyphs: Vec<u32> = font.shapes().collect();
+        glyphs.sort();
+        let mut shown: Vec<u32> = patterns
+            .iter()
+            .map(|pattern| self.unscramble(pattern.iter().copied()))
+            .collect();
+        shown.sort();
+        shown == glyphs
+    }
+
+    // whether the signal patterns show different glyphs of `font`, and the
+    // output patterns glyphs too, with this wiring
+    pub(crate) fn fits(
+        &self,
+        font: &Font,
+        signal_patterns: &[HashSet<char>],
+        output_patterns: &[HashSet<char>],
+    ) -> bool {
+        let glyph = |pattern: &HashSet<char>| {
+            let known = pattern
+                .iter()
+                .all(|&wire| is_wire(wire, self.segments.len()));
+            known
+                .then(|| font.glyph(self.unscramble(pattern.iter().copied())))
+                .flatten()
+        };
+        let mut shown = HashSet::new();
+        signal_patterns
+            .iter()
+            .all(|pattern| glyph(pattern).is_some_and(|glyph| shown.insert(glyph)))
+            && output_patterns
+                .iter()
+                .all(|pattern| glyph(pattern).is_some())
+    }
+
+    /// Glyphs of `font` shown by the `patterns`.
+    pub fn read_glyphs<P: AsRef<str>>(
+        &self,
+        font: &Font,
+        patterns: &[P],
+    ) -> Result<String, DecodeError> {
+        patterns
+            .iter()
+            .map(|pattern| {
+                self.glyph(font, pattern.as_ref())
+                    .ok_or_else(|| DecodeError::UnknownGlyph {
+                        pattern: pattern.as_ref().to_string(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Number shown by the output value of `note`.
+    pub fn read(&self, note: &NoteEntry) -> Result<u32, DecodeError> {
+        let digits = self.read_glyphs(&Font::seven_segment_digits(), &note.output_value)?;
+        // four digits at most, as parsed
+        Ok(digits
+            .chars()
+            .fold(0, |number, digit| number * 10 + digit.to_digit(10).unwrap()))
+    }
+}
+
+// index of `wire`, from `a`, which must be a lowercase letter
+pub(crate) fn wire_index(wire: char) -> usize {
+    (wire as u8 - b'a') as usize
+}
+
+// whether `wire` is one of the `wires` first letters
+pub(crate) fn is_wire(wire: char, wires: usize) -> bool {
+    wire.is_ascii_lowercase() && wire_index(wire) < wires
+}
+
+// every ordering of the 7 segments, i.e. every possible wiring
+pub(crate) fn wirings() -> Vec<Wiring> {
+    fn extend(segments: &mut Vec<u8>, wirings: &mut Vec<Wiring>) {
+        if segments.len() == 7 {
+            wirings.push(Wiring {
+                segments: segments.clone(),
+            });
+            return;
+        }
+        for segment in 0..7 {
+            if !segments.contains(&segment) {
+                segments.push(segment);
+                extend(segments, wirings);
+                segments.pop();
+            }
+        }
+    }
+    let mut wirings = Vec::with_capacity(5040);
+    extend(&mut Vec::with_capacity(7), &mut wirings);
+    wirings
+}
+
+/// Wiring turning the signal `patterns` into exactly the ten digits, found by
+/// trying all 5040 of them. Unlike deducing segments one after the other, it
+/// never assumes that the patterns are consistent, and reports when they are
+/// not.
+pub fn solve_wiring(patterns: &[HashSet<char>]) -> Result<Wiring, DecodeError> {
+    let digits = Font::seven_segment_digits();
+    let mut matching = wirings()
+        .into_iter()
+        .filter(|wiring| wiring.shows_glyphs(&digits, patterns));
+    match (matching.next(), matching.count()) {
+        (None, _) => Err(DecodeError::NoWiring),
+        (Some(wiring), 0) => Ok(wiring),
+        (Some(_), others) => Err(DecodeError::AmbiguousWiring {
+            wirings: others + 1,
+        }),
+    }
+}
+
+/// Number shown by the output value of `note`, checking every step of the
+/// decoding.
+pub fn decode_note(note: &NoteEntry) -> Result<u32, DecodeError> {
+    solve_wiring(&note.signal_patterns)?.read(note)
+}
+
+#[cfg(test)]
+use crate::patterns;
+
+#[test]
+fn test_wirings() {
+    // WHEN
+    let wirings = wirings();
+
+    // THEN
+    assert_eq!(wirings.len(), 5040);
+    let distinct: HashSet<Vec<u8>> = wirings.into_iter().map(|wiring| wiring.segments).collect();
+    assert_eq!(distinct.len(), 5040);
+}
+
+#[test]
+fn test_solve_wiring() {
+    // GIVEN
+    let input =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+    let note = crate::parse_note_entry(input).unwrap();
+
+    // WHEN
+    let wiring = solve_wiring(&note.signal_patterns).unwrap();
+
+    // THEN
+    let segments: Option<String> = "abcdefg".chars().map(|wire| wiring.segment(wire)).collect();
+    assert_eq!(segments.as_deref(), Some("cfgabde"));
+    assert_eq!(wiring.digit("ab"), Some(1));
+    assert_eq!(wiring.digit("abc"), None);
+    assert_eq!(wiring.read(&note), Ok(5353));
+    assert_eq!(decode_note(&note), Ok(5353));
+}
+
+#[test]
+fn test_solve_wiring_reports_inconsistent_patterns() {
+    // GIVEN the example patterns, with a wire off in the 3
+    let broken = patterns("acedgfb cdfbe gcdfa fbca dab cefabd cdfgeb eafb cagedb ab");
+    let missing = patterns("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb");
+    let duplicated = patterns("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb ab ab");
+
+    // WHEN
+    let r = [broken, missing, duplicated].map(|patterns| solve_wiring(&patterns));
+
+    // THEN
+    assert_eq!(r, [(); 3].map(|_| Err(DecodeError::NoWiring)));
+}
+
+#[test]
+fn test_decode_note_reports_unknown_output_digit() {
+    // GIVEN
+    let input =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fca cdfeb cdbaf";
+    let note = crate::parse_note_entry(input).unwrap();
+
+    // WHEN
+    let r = decode_note(&note);
+
+    // THEN
+    assert_eq!(
+        r,
+        Err(DecodeError::UnknownGlyph {
+            pattern: "fca".to_string()
+        })
+    );
+    assert_eq!(
+        r.unwrap_err().to_string(),
+        "output pattern 'fca' is not a glyph"
+    );
+}
+
+#[test]
+fn test_unknown_wires() {
+    // GIVEN
+    let font = Font::seven_segment_digits();
+    let wiring = Wiring::from_segments(vec![2, 5, 6, 0, 1, 3, 4]);
+
+    // WHEN
+    let r = wiring.read_glyphs(&font, &["aB"]);
+
+    // THEN
+    assert_eq!(wiring.glyph(&font, "aB"), None);
+    assert_eq!(wiring.glyph(&font, "ah"), None);
+    assert_eq!(wiring.segment('A'), None);
+    assert_eq!(wiring.segment('h'), None);
+    assert_eq!(wiring.wire('h'), None);
+    assert_eq!(
+        r,
+        Err(DecodeError::UnknownGlyph {
+            pattern: "aB".to_string()
+        })
+    );
+}